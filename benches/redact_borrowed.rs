@@ -0,0 +1,33 @@
+//! Compares [`Redactor::redact`] against [`Redactor::redact_borrowed`], for data that redaction changes and data it
+//! leaves as-is.
+//!
+//! Run with `cargo bench --bench redact_borrowed`.
+
+use std::{hint::black_box, time::Instant};
+use veil::redactor::Redactor;
+
+const ITERATIONS: u32 = 1_000_000;
+const CHANGED: &str = "john.doe@prima.it";
+const UNCHANGED: &str = "--:--";
+
+fn main() {
+    let redactor = Redactor::builder().partial().build().unwrap();
+
+    for (label, data) in [("changed", CHANGED), ("unchanged", UNCHANGED)] {
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            black_box(redactor.redact(black_box(data).to_string()));
+        }
+        println!(
+            "redact ({label}):{:>pad$?}",
+            start.elapsed() / ITERATIONS,
+            pad = 12 - label.len()
+        );
+
+        let start = Instant::now();
+        for _ in 0..ITERATIONS {
+            black_box(redactor.redact_borrowed(black_box(data)));
+        }
+        println!("redact_borrowed ({label}): {:?}", start.elapsed() / ITERATIONS);
+    }
+}
@@ -0,0 +1,29 @@
+//! Compares [`Redactor::redact`] against [`Redactor::redact_owned`] reusing a single buffer.
+//!
+//! Run with `cargo bench --bench redact_owned`.
+
+use std::{hint::black_box, time::Instant};
+use veil::redactor::Redactor;
+
+const ITERATIONS: u32 = 1_000_000;
+const DATA: &str = "john.doe@prima.it";
+
+fn main() {
+    let redactor = Redactor::builder().partial().build().unwrap();
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(redactor.redact(black_box(DATA).to_string()));
+    }
+    println!("redact:       {:?}", start.elapsed() / ITERATIONS);
+
+    let mut buffer = String::with_capacity(DATA.len() * 2);
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        buffer.clear();
+        buffer.push_str(black_box(DATA));
+        buffer = redactor.redact_owned(buffer);
+        black_box(&buffer);
+    }
+    println!("redact_owned: {:?}", start.elapsed() / ITERATIONS);
+}
@@ -0,0 +1,80 @@
+use std::fmt;
+
+pub enum Account {
+    Personal {
+        owner: String,
+        iban: String,
+    },
+    Business {
+        company: String,
+        vat_number: String,
+        iban: String,
+    },
+}
+
+// Before: a hand-written `Debug` implementation that leaks everything.
+//
+// impl fmt::Debug for Account {
+//     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+//         match self {
+//             Account::Personal { owner, iban } => {
+//                 let mut s = f.debug_struct("Personal");
+//                 s.field("owner", owner);
+//                 s.field("iban", iban);
+//                 s.finish()
+//             }
+//             Account::Business { company, vat_number, iban } => {
+//                 let mut s = f.debug_struct("Business");
+//                 s.field("company", company);
+//                 s.field("vat_number", vat_number);
+//                 s.field("iban", iban);
+//                 s.finish()
+//             }
+//         }
+//     }
+// }
+
+// After: the same implementation, with the sensitive fields marked using `veil_field!`.
+#[veil::redact_impl]
+impl fmt::Debug for Account {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Account::Personal { owner, iban } => {
+                let mut s = f.debug_struct("Personal");
+                veil_field!(s, "owner", owner, partial);
+                veil_field!(s, "iban", iban, partial, with = 'X');
+                s.finish()
+            }
+            Account::Business {
+                company,
+                vat_number,
+                iban,
+            } => {
+                let mut s = f.debug_struct("Business");
+                s.field("company", company);
+                veil_field!(s, "vat_number", vat_number, fixed = 8);
+                veil_field!(s, "iban", iban, partial, with = 'X');
+                s.finish()
+            }
+        }
+    }
+}
+
+fn main() {
+    println!(
+        "{:#?}",
+        Account::Personal {
+            owner: "John Doe".to_string(),
+            iban: "GB33BUKB20201555555555".to_string(),
+        }
+    );
+
+    println!(
+        "{:#?}",
+        Account::Business {
+            company: "Prima".to_string(),
+            vat_number: "IT12345678901".to_string(),
+            iban: "IT60X0542811101000000123456".to_string(),
+        }
+    );
+}
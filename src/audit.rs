@@ -0,0 +1,55 @@
+#![cfg_attr(docsrs, doc(cfg(feature = "audit")))]
+
+//! Makes it possible to observe which fields veil redacts at runtime, e.g. for compliance logging.
+//!
+//! Every time a `#[derive(Redact)]` item is formatted with [`Debug`], the registered hook is called once for each
+//! field that is redacted, identifying the field by its path and type.
+//!
+//! **The values of redacted fields are never passed to the hook.**
+//!
+//! ```
+//! veil::audit::set_hook(|event| {
+//!     eprintln!("redacted {} ({})", event.field, event.ty);
+//! })
+//! .unwrap();
+//! ```
+
+use once_cell::sync::OnceCell;
+
+/// A field that was redacted.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(docsrs, doc(cfg(feature = "audit")))]
+pub struct RedactionEvent {
+    /// The path of the redacted field, such as `Struct::field`, `Struct::0` or `Enum::Variant::field`.
+    pub field: &'static str,
+
+    /// The name of the field's type, as returned by [`std::any::type_name`].
+    pub ty: &'static str,
+}
+
+type Hook = Box<dyn Fn(&RedactionEvent) + Send + Sync>;
+
+static HOOK: OnceCell<Hook> = OnceCell::new();
+
+#[cfg_attr(docsrs, doc(cfg(feature = "audit")))]
+/// Registers the global redaction audit hook.
+///
+/// Can only be called once, otherwise `Err` will be returned.
+pub fn set_hook(hook: impl Fn(&RedactionEvent) + Send + Sync + 'static) -> Result<(), &'static str> {
+    HOOK.set(Box::new(hook))
+        .map_err(|_| "a redaction audit hook has already been set")
+}
+
+#[doc(hidden)]
+/// Called by the generated [`Debug`] implementation for every redacted field.
+pub fn record(field: &'static str, ty: &'static str) {
+    #[cfg(feature = "toggle")]
+    if crate::toggle::get_redaction_behavior().is_plaintext() {
+        // Nothing is actually redacted
+        return;
+    }
+
+    if let Some(hook) = HOOK.get() {
+        hook(&RedactionEvent { field, ty });
+    }
+}
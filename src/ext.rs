@@ -0,0 +1,138 @@
+//! The [`RedactExt`] extension trait allows for redacting any value inline, without building a
+//! [`Redactor`](crate::redactor::Redactor) first.
+
+use crate::private::{RedactFlags, RedactionFormatter, RedactionLength, RedactionTarget};
+use std::fmt::{Debug, Display};
+
+/// A borrowed value that, when formatted as [`Debug`] or [`Display`] (if implemented for `T`), will be redacted.
+///
+/// See [`RedactExt`] for more information.
+#[derive(Clone, Copy)]
+pub struct Redacted<'a, T: ?Sized> {
+    data: &'a T,
+    flags: RedactFlags,
+}
+impl<T> Display for Redacted<'_, T>
+where
+    T: Display + ?Sized,
+{
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(
+            &RedactionFormatter {
+                this: RedactionTarget::Display(&self.data),
+                flags: self.flags,
+                specialization: None,
+            },
+            fmt,
+        )
+    }
+}
+impl<T> Debug for Redacted<'_, T>
+where
+    T: Debug + ?Sized,
+{
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(
+            &RedactionFormatter {
+                this: RedactionTarget::Debug {
+                    this: &self.data,
+                    alternate: fmt.alternate(),
+                },
+                flags: self.flags,
+                specialization: None,
+            },
+            fmt,
+        )
+    }
+}
+
+/// Redacts any value inline, e.g. in a `format!` or `tracing` call.
+///
+/// Each method borrows the value and returns a [`Redacted`] wrapper that redacts it when formatted as [`Display`] or
+/// [`Debug`], whichever the value implements. Like [`Redactor::wrap`](crate::redactor::Redactor::wrap), the whole
+/// output is redacted, so prefer deriving [`Redact`](crate::Redact) for your own types.
+///
+/// # Example
+///
+/// ```rust
+/// use veil::RedactExt;
+///
+/// let email = "john.doe@prima.it";
+/// tracing::info!("sending receipt to {}", email.redacted_partial());
+///
+/// assert_eq!(email.redacted().to_string(), "****.***@*****.**");
+/// assert_eq!(email.redacted_partial().to_string(), "joh*.***@****a.it");
+/// assert_eq!(email.redacted_with('X').to_string(), "XXXX.XXX@XXXXX.XX");
+/// assert_eq!(format!("{:?}", email.redacted_partial()), "\"joh*.***@****a.it\"");
+/// ```
+pub trait RedactExt {
+    /// Redacts the entire value.
+    ///
+    /// Equivalent to `#[redact]` when deriving.
+    fn redacted(&self) -> Redacted<'_, Self> {
+        Redacted {
+            data: self,
+            flags: RedactFlags {
+                redact_length: RedactionLength::Full,
+                redact_char: RedactFlags::DEFAULT_REDACT_CHAR,
+                redact_cycle: None,
+                digits_only: false,
+                cjk: false,
+                preserve_class: None,
+                pad: None,
+                min_mask_len: None,
+                min_partial_chars: None,
+                expose_side: None,
+                secret: false,
+                hash: None,
+            },
+        }
+    }
+
+    /// Redacts the value, exposing a small part of its beginning and end if it's long enough.
+    ///
+    /// Equivalent to `#[redact(partial)]` when deriving.
+    fn redacted_partial(&self) -> Redacted<'_, Self> {
+        Redacted {
+            data: self,
+            flags: RedactFlags {
+                redact_length: RedactionLength::Partial,
+                redact_char: RedactFlags::DEFAULT_REDACT_CHAR,
+                redact_cycle: None,
+                digits_only: false,
+                cjk: false,
+                preserve_class: None,
+                pad: None,
+                min_mask_len: None,
+                min_partial_chars: None,
+                expose_side: None,
+                secret: false,
+                hash: None,
+            },
+        }
+    }
+
+    /// Redacts the entire value with the given character.
+    ///
+    /// Equivalent to `#[redact(with = '...')]` when deriving.
+    fn redacted_with(&self, char: char) -> Redacted<'_, Self> {
+        Redacted {
+            data: self,
+            flags: RedactFlags {
+                redact_length: RedactionLength::Full,
+                redact_char: char,
+                redact_cycle: None,
+                digits_only: false,
+                cjk: false,
+                preserve_class: None,
+                pad: None,
+                min_mask_len: None,
+                min_partial_chars: None,
+                expose_side: None,
+                secret: false,
+                hash: None,
+            },
+        }
+    }
+}
+impl<T: ?Sized> RedactExt for T {}
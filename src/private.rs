@@ -4,6 +4,7 @@ use std::{
     num::NonZeroU8,
 };
 
+#[derive(Clone, Copy)]
 pub enum RedactSpecialization {
     /// Whether the type we're redacting is an [`Option<T>`] or not. Poor man's specialization! This is detected
     /// by the proc macro reading the path to the type, so it's not perfect.
@@ -19,9 +20,31 @@ pub enum RedactSpecialization {
     /// * Once trait upcasting is stabilized, we could use it to upcast the dyn Debug pointer to a dyn Any and then
     ///   downcast it to a concrete [`Option<T>`] and redact it directly.
     Option,
+
+    /// Whether the type we're redacting is an error, such as a `Box<dyn Error>` or an `anyhow::Error`. Like
+    /// [`RedactSpecialization::Option`], this is detected by the proc macro reading the path to the type.
+    ///
+    /// Error messages often embed sensitive data, so only the name of the error type at the start of its [`Debug`]
+    /// output is kept, e.g. `ParseError("******")`.
+    Error,
+
+    /// Whether the type we're redacting is a [`Vec<T>`], detected like [`RedactSpecialization::Option`].
+    ///
+    /// Each element of its [`Debug`] output is redacted on its own, so partial redaction exposes the ends of every
+    /// element instead of the ends of the whole list.
+    Vec,
+
+    /// Whether the type we're redacting is a map, such as a `HashMap<K, V>`, and only its values should be redacted
+    /// with `#[redact(values)]`.
+    ///
+    /// The keys in its [`Debug`] output are kept as-is, and each value is redacted on its own like the elements of a
+    /// [`RedactSpecialization::Vec`].
+    Map,
 }
 
-#[derive(Clone, Copy)]
+/// How much of the data is redacted, see [`Redactor::mask_mode`](crate::redactor::Redactor::mask_mode).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum RedactionLength {
     /// Redact the entire data.
     Full,
@@ -29,8 +52,18 @@ pub enum RedactionLength {
     /// Redact a portion of the data.
     Partial,
 
+    /// Redact a portion of the data, exposing the given percentage of it at each end.
+    PartialPercent(u8),
+
+    /// Redact a portion of the data, exposing at most the given number of characters at each end instead of the
+    /// default.
+    PartialExpose(u8),
+
     /// Whether to redact with a fixed width, ignoring the length of the data.
     Fixed(NonZeroU8),
+
+    /// Redact exactly this many characters at the beginning and end of the data, exposing the middle.
+    MaskEnds { prefix: u8, suffix: u8 },
 }
 
 #[derive(Clone, Copy)]
@@ -40,69 +73,534 @@ pub struct RedactFlags {
 
     /// What character to use for redacting.
     pub redact_char: char,
+
+    /// Characters to cycle through for redacting instead of `redact_char`, from `#[redact(with = "...", cycle)]`.
+    pub redact_cycle: Option<&'static str>,
+
+    /// Whether to only redact digits, leaving letters, units and punctuation as-is.
+    pub digits_only: bool,
+
+    /// Whether partial redaction only exposes the first character of data made up of CJK characters, such as a name.
+    pub cjk: bool,
+
+    /// Characters to redact letters and digits with instead of `redact_char`, from `#[redact(preserve_class)]`.
+    pub preserve_class: Option<PreserveClass>,
+
+    /// Pads the redacted output with spaces to a minimum width, from `#[redact(pad = ...)]`.
+    pub pad: Option<Padding>,
+
+    /// The minimum number of redaction characters to fully redact data with, so that short data can't be told apart
+    /// from longer data, from [`RedactorBuilder::min_mask_len`](crate::redactor::RedactorBuilder::min_mask_len).
+    pub min_mask_len: Option<NonZeroU8>,
+
+    /// The minimum number of redactable characters for data to be partially redacted instead of fully, from
+    /// `#[redact(partial, min = ...)]`.
+    pub min_partial_chars: Option<NonZeroU8>,
+
+    /// Which end partial redaction exposes, from `#[redact(partial, expose_start)]` or `#[redact(partial, expose_end)]`.
+    /// Both ends are exposed if `None`.
+    pub expose_side: Option<ExposeSide>,
+
+    /// Whether to keep redacting when redaction has been disabled with the `toggle` feature, from `#[redact(secret)]`.
+    pub secret: bool,
+
+    /// Replaces the data with the hex digest of its text instead of masking it, so equal data can be correlated, from
+    /// `#[redact(hash = "sha256")]`. Requires the `hash` feature.
+    pub hash: Option<HashAlgo>,
 }
 impl RedactFlags {
-    /// How many characters must a word be for it to be partially redacted?
+    /// The character to redact with unless another is configured, set by the `profile-*` features.
+    pub(crate) const DEFAULT_REDACT_CHAR: char = if cfg!(feature = "profile-strict") { '#' } else { '*' };
+
+    /// How many characters must a word be for it to be partially redacted? Set by the `profile-*` features.
     ///
     /// Words smaller than this many characters (NOT bytes) will be fully redacted.
-    const MIN_PARTIAL_CHARS: usize = 5;
+    const MIN_PARTIAL_CHARS: usize = if cfg!(feature = "profile-lenient") {
+        4
+    } else if cfg!(feature = "profile-strict") {
+        8
+    } else {
+        5
+    };
 
-    /// Maximum number of characters to expose at the beginning and end of a partial redact.
-    const MAX_PARTIAL_EXPOSE: usize = 3;
+    /// Maximum number of characters to expose at the beginning and end of a partial redact. Set by the `profile-*`
+    /// features.
+    const MAX_PARTIAL_EXPOSE: usize = if cfg!(feature = "profile-lenient") {
+        4
+    } else if cfg!(feature = "profile-strict") {
+        2
+    } else {
+        3
+    };
 
-    pub(crate) fn redact_partial(&self, fmt: &mut std::fmt::Formatter, to_redact: &str) -> std::fmt::Result {
-        let count = to_redact.chars().filter(|char| char.is_alphanumeric()).count();
+    /// How many redactable characters a partial redaction exposes at both the beginning and the end of data
+    /// containing `count` redactable characters, with the default `profile-standard` settings:
+    ///
+    /// | Redactable characters | Exposed at each end        |
+    /// |-----------------------|----------------------------|
+    /// | 0 to 4                | 0, it's redacted entirely. |
+    /// | 5                     | 1                          |
+    /// | 6 to 8                | 2                          |
+    /// | 9 or more             | 3                          |
+    pub(crate) const fn partial_exposed(count: usize) -> usize {
         if count < Self::MIN_PARTIAL_CHARS {
-            for char in to_redact.chars() {
-                if char.is_alphanumeric() {
-                    fmt.write_char(self.redact_char)?;
+            0
+        } else {
+            Self::partial_exposed_at_most(count, Self::MAX_PARTIAL_EXPOSE)
+        }
+    }
+
+    /// How many of `count` redactable characters are exposed at each end of data long enough to be partially
+    /// redacted, up to `max`, e.g. from `#[redact(partial, expose = ...)]`. Never more than a third of the data is
+    /// exposed at each end.
+    const fn partial_exposed_at_most(count: usize, max: usize) -> usize {
+        if count / 3 < max {
+            count / 3
+        } else {
+            max
+        }
+    }
+
+    /// Whether this character is considered sensitive and should be redacted.
+    #[inline(always)]
+    fn is_redactable(&self, char: char) -> bool {
+        if self.digits_only {
+            char.is_numeric()
+        } else {
+            char.is_alphanumeric()
+        }
+    }
+
+    /// The character to redact the given character with, which is taken from `redact_chars` unless the character's
+    /// category is preserved.
+    #[inline(always)]
+    fn replacement(&self, char: char, redact_chars: &mut RedactChars) -> char {
+        redact_chars.masked += 1;
+        match &self.preserve_class {
+            Some(preserve_class) => preserve_class.replace(char),
+            None => redact_chars.next(),
+        }
+    }
+
+    /// Whether `redact_full` replaces the given character.
+    #[inline(always)]
+    pub(crate) fn is_masked(&self, char: char) -> bool {
+        !char.is_whitespace() && self.is_redactable(char)
+    }
+
+    /// How many characters `redact_full` replaces, and how many redaction characters it adds after the last of them to
+    /// reach `min_mask_len`. Data with nothing to replace is left as-is.
+    fn mask_floor(&self, to_redact: Units) -> (usize, usize) {
+        let Some(min_mask_len) = self.min_mask_len else {
+            return (0, 0);
+        };
+        let count = to_redact.filter(|unit| self.is_masked(unit.char)).count();
+        (count, (min_mask_len.get() as usize).saturating_sub(count))
+    }
+
+    /// The characters to redact with, one for each redacted character.
+    fn redact_chars(&self) -> RedactChars {
+        RedactChars {
+            char: self.redact_char,
+            cycle: self.redact_cycle.map(|cycle| cycle.chars().cycle()),
+            masked: 0,
+        }
+    }
+
+    pub(crate) fn redact_partial(
+        &self,
+        fmt: &mut dyn Write,
+        to_redact: Units,
+        redact_chars: &mut RedactChars,
+    ) -> std::fmt::Result {
+        let count = to_redact.filter(|unit| self.is_redactable(unit.char)).count();
+
+        // The number of characters at the beginning and end we'll EXPOSE (NOT redact over)
+        let (prefix_count, suffix_count) = if self.cjk
+            && count >= 2
+            && to_redact
+                .filter(|unit| self.is_redactable(unit.char))
+                .all(|unit| is_cjk_name_char(unit.char))
+        {
+            // Only the first character of a CJK name is exposed, which is usually the surname.
+            (1, 0)
+        } else if count
+            < self
+                .min_partial_chars
+                .map_or(Self::MIN_PARTIAL_CHARS, |min| min.get() as usize)
+        {
+            (0, 0)
+        } else {
+            let redact_count = match self.redact_length {
+                RedactionLength::PartialPercent(pct) => count * pct as usize / 100,
+                RedactionLength::PartialExpose(max) => Self::partial_exposed_at_most(count, max as usize),
+                _ => Self::partial_exposed_at_most(count, Self::MAX_PARTIAL_EXPOSE),
+            };
+            (redact_count, redact_count)
+        };
+
+        // Data that would be exposed entirely is redacted entirely instead, whatever the configuration.
+        let (prefix_count, suffix_count) = if prefix_count.saturating_add(suffix_count) >= count {
+            (0, 0)
+        } else {
+            match self.expose_side {
+                Some(ExposeSide::Start) => (prefix_count, 0),
+                Some(ExposeSide::End) => (0, suffix_count),
+                None => (prefix_count, suffix_count),
+            }
+        };
+
+        let mut prefix_gas = prefix_count;
+        let mut middle_gas = count - prefix_count - suffix_count;
+        for unit in to_redact {
+            if self.is_redactable(unit.char) {
+                if prefix_gas > 0 {
+                    prefix_gas -= 1;
+                    fmt.write_str(unit.text)?;
+                } else if middle_gas > 0 {
+                    middle_gas -= 1;
+                    fmt.write_char(self.replacement(unit.char, redact_chars))?;
                 } else {
-                    fmt.write_char(char)?;
+                    fmt.write_str(unit.text)?;
                 }
+            } else {
+                fmt.write_str(unit.text)?;
             }
-        } else {
-            // The number of characters (prefix and suffix) we'll EXPOSE (NOT redact over)
-            let redact_count = (count / 3).min(Self::MAX_PARTIAL_EXPOSE);
-
-            let mut prefix_gas = redact_count;
-            let mut middle_gas = count - redact_count - redact_count;
-            for char in to_redact.chars() {
-                if char.is_alphanumeric() {
-                    if prefix_gas > 0 {
-                        prefix_gas -= 1;
-                        fmt.write_char(char)?;
-                    } else if middle_gas > 0 {
-                        middle_gas -= 1;
-                        fmt.write_char(self.redact_char)?;
-                    } else {
-                        fmt.write_char(char)?;
-                    }
+        }
+        Ok(())
+    }
+
+    /// Redacts `prefix` characters at the beginning and `suffix` characters at the end, exposing everything in between.
+    ///
+    /// If that would redact every character, the data is redacted entirely instead.
+    pub(crate) fn redact_ends(
+        &self,
+        fmt: &mut dyn Write,
+        to_redact: Units,
+        redact_chars: &mut RedactChars,
+        prefix: usize,
+        suffix: usize,
+    ) -> std::fmt::Result {
+        let count = to_redact.filter(|unit| self.is_redactable(unit.char)).count();
+        if prefix + suffix >= count {
+            return self.redact_full(fmt, to_redact, redact_chars);
+        }
+
+        let mut index = 0;
+        for unit in to_redact {
+            if self.is_redactable(unit.char) {
+                if index < prefix || index >= count - suffix {
+                    fmt.write_char(self.replacement(unit.char, redact_chars))?;
                 } else {
-                    fmt.write_char(char)?;
+                    fmt.write_str(unit.text)?;
                 }
+                index += 1;
+            } else {
+                fmt.write_str(unit.text)?;
             }
         }
         Ok(())
     }
 
-    pub(crate) fn redact_full(&self, fmt: &mut std::fmt::Formatter, to_redact: &str) -> std::fmt::Result {
-        for char in to_redact.chars() {
-            if char.is_whitespace() || !char.is_alphanumeric() {
-                fmt.write_char(char)?;
+    #[cfg(not(feature = "unicode-width"))]
+    pub(crate) fn redact_full(
+        &self,
+        fmt: &mut dyn Write,
+        to_redact: Units,
+        redact_chars: &mut RedactChars,
+    ) -> std::fmt::Result {
+        let (mut remaining, extra) = self.mask_floor(to_redact);
+        for unit in to_redact {
+            if !self.is_masked(unit.char) {
+                fmt.write_str(unit.text)?;
             } else {
-                fmt.write_char(self.redact_char)?;
+                fmt.write_char(self.replacement(unit.char, redact_chars))?;
+
+                remaining = remaining.saturating_sub(1);
+                if remaining == 0 {
+                    for _ in 0..extra {
+                        fmt.write_char(redact_chars.next())?;
+                    }
+                }
             }
         }
         Ok(())
     }
 
-    pub(crate) fn redact_fixed(fmt: &mut std::fmt::Formatter, width: usize, char: char) -> std::fmt::Result {
+    /// Replaces the data with the hex digest of its unescaped text. The quotes of a string's `Debug` output are kept
+    /// around the digest rather than hashed, so that it has the same digest as the string's `Display` output.
+    #[cfg(feature = "hash")]
+    pub(crate) fn redact_hash(
+        &self,
+        fmt: &mut dyn Write,
+        to_redact: Units,
+        redact_chars: &mut RedactChars,
+        algo: HashAlgo,
+    ) -> std::fmt::Result {
+        use sha2::Digest;
+
+        let (quote, to_hash) = match to_redact
+            .remaining
+            .strip_prefix('"')
+            .and_then(|inner| inner.strip_suffix('"'))
+        {
+            Some(inner) if to_redact.escaped => ("\"", Units::new(inner, true)),
+            _ => ("", to_redact),
+        };
+
+        let mut buf = [0; 4];
+        let digest = match algo {
+            HashAlgo::Sha256 => {
+                let mut hasher = sha2::Sha256::new();
+                for unit in to_hash {
+                    hasher.update(unit.char.encode_utf8(&mut buf).as_bytes());
+                    redact_chars.masked += 1;
+                }
+                hasher.finalize()
+            }
+        };
+
+        fmt.write_str(quote)?;
+        for byte in digest {
+            write!(fmt, "{byte:02x}")?;
+        }
+        fmt.write_str(quote)
+    }
+
+    /// Like the default `redact_full`, but each run of redacted characters is replaced with enough redaction
+    /// characters to cover the same number of terminal columns.
+    #[cfg(feature = "unicode-width")]
+    pub(crate) fn redact_full(
+        &self,
+        fmt: &mut dyn Write,
+        to_redact: Units,
+        redact_chars: &mut RedactChars,
+    ) -> std::fmt::Result {
+        use unicode_width::UnicodeWidthChar;
+
+        let (mut remaining, extra) = self.mask_floor(to_redact);
+
+        // How many columns the current run of redacted characters takes up.
+        let mut columns = 0;
+        for unit in to_redact {
+            if !self.is_masked(unit.char) {
+                write_columns(fmt, columns, redact_chars)?;
+                columns = 0;
+
+                fmt.write_str(unit.text)?;
+                continue;
+            } else if self.preserve_class.is_some() {
+                // Each character keeps its category, so it's replaced by exactly one character regardless of its width.
+                fmt.write_char(self.replacement(unit.char, redact_chars))?;
+            } else {
+                redact_chars.masked += 1;
+                columns += unit.char.width().unwrap_or(0);
+            }
+
+            // The extra redaction characters take up a column each.
+            remaining = remaining.saturating_sub(1);
+            if remaining == 0 {
+                columns += extra;
+            }
+        }
+        write_columns(fmt, columns, redact_chars)
+    }
+
+    #[cfg(not(feature = "unicode-width"))]
+    pub(crate) fn redact_fixed(&self, fmt: &mut std::fmt::Formatter, width: usize) -> std::fmt::Result {
+        let mut redact_chars = self.redact_chars();
         let mut buf = String::with_capacity(width);
         for _ in 0..width {
-            buf.push(char);
+            buf.push(redact_chars.next());
         }
         fmt.write_str(&buf)
     }
+
+    /// With the `unicode-width` feature, the fixed width is measured in terminal columns.
+    #[cfg(feature = "unicode-width")]
+    pub(crate) fn redact_fixed(&self, fmt: &mut std::fmt::Formatter, width: usize) -> std::fmt::Result {
+        write_columns(fmt, width, &mut self.redact_chars())
+    }
+}
+
+/// The only end of the data that partial redaction exposes, from `#[redact(partial, expose_start)]` or
+/// `#[redact(partial, expose_end)]`.
+#[derive(Clone, Copy, Debug)]
+pub enum ExposeSide {
+    Start,
+    End,
+}
+
+/// The algorithm that `#[redact(hash = "...")]` digests data with.
+#[derive(Clone, Copy, Debug)]
+pub enum HashAlgo {
+    Sha256,
+}
+
+/// The characters that `#[redact(preserve_class)]` redacts letters and digits with, so redacted data keeps its shape,
+/// e.g. `AB-1234` is redacted as `xx-0000`.
+#[derive(Clone, Copy, Debug)]
+pub struct PreserveClass {
+    pub letter: char,
+    pub digit: char,
+}
+impl PreserveClass {
+    /// Returns the character of the same category to redact the given alphanumeric character with.
+    #[inline(always)]
+    fn replace(&self, char: char) -> char {
+        if char.is_numeric() {
+            self.digit
+        } else {
+            self.letter
+        }
+    }
+}
+
+/// Pads redacted output with spaces to a minimum number of characters, e.g. for fixed-width log columns.
+#[derive(Clone, Copy, Debug)]
+pub struct Padding {
+    pub width: u8,
+    pub align: std::fmt::Alignment,
+}
+impl Padding {
+    /// Writes the redacted output, padded to the width. Output that is already as wide is written as-is.
+    fn write(&self, fmt: &mut dyn Write, redacted: &str) -> std::fmt::Result {
+        let fill = (self.width as usize).saturating_sub(redacted.chars().count());
+        let (before, after) = match self.align {
+            std::fmt::Alignment::Left => (0, fill),
+            std::fmt::Alignment::Right => (fill, 0),
+            std::fmt::Alignment::Center => (fill / 2, fill - fill / 2),
+        };
+        write!(fmt, "{:before$}{redacted}{:after$}", "", "")
+    }
+}
+
+/// Whether a character is a Han ideograph or a Hangul syllable, which CJK names are written in.
+fn is_cjk_name_char(char: char) -> bool {
+    matches!(
+        char,
+        '\u{3400}'..='\u{4DBF}'
+            | '\u{4E00}'..='\u{9FFF}'
+            | '\u{F900}'..='\u{FAFF}'
+            | '\u{20000}'..='\u{3134F}'
+            | '\u{AC00}'..='\u{D7A3}'
+    )
+}
+
+/// Yields the character to redact each redacted character with.
+///
+/// This is always the same character, unless it cycles through the characters of a `#[redact(with = "...", cycle)]`
+/// pattern, continuing across words, e.g. `XYXY XYX`.
+pub(crate) struct RedactChars {
+    char: char,
+    cycle: Option<std::iter::Cycle<std::str::Chars<'static>>>,
+
+    /// How many characters of the data have been redacted so far.
+    masked: usize,
+}
+impl RedactChars {
+    fn next(&mut self) -> char {
+        self.cycle.as_mut().and_then(Iterator::next).unwrap_or(self.char)
+    }
+}
+
+/// A logical character of the data being redacted.
+#[derive(Clone, Copy)]
+pub(crate) struct Unit<'a> {
+    /// How the character is written in the data, e.g. `\u{1b}` in [`Debug`] output.
+    text: &'a str,
+
+    /// The character itself.
+    char: char,
+}
+
+/// Splits the data being redacted into logical characters.
+///
+/// [`Debug`] output escapes some characters, such as `\n`, `\0` or `\u{1b}`. These are kept together as a single
+/// character, so that redaction never splits an escape sequence and the output stays valid [`Debug`] output.
+#[derive(Clone, Copy)]
+pub(crate) struct Units<'a> {
+    remaining: &'a str,
+
+    /// Whether the data is [`Debug`] output that may contain escape sequences.
+    escaped: bool,
+}
+impl<'a> Units<'a> {
+    pub(crate) fn new(data: &'a str, escaped: bool) -> Self {
+        Self {
+            remaining: data,
+            escaped,
+        }
+    }
+
+    /// Parses an escape sequence produced by [`char::escape_debug`] at the start of `data`.
+    fn escape_sequence(data: &str) -> Option<Unit<'_>> {
+        let mut chars = data.strip_prefix('\\')?.chars();
+        let char = match chars.next()? {
+            't' => '\t',
+            'r' => '\r',
+            'n' => '\n',
+            '0' => '\0',
+            char @ ('\\' | '\'' | '"') => char,
+            'u' => {
+                let hex = data.get(2..)?.strip_prefix('{')?;
+                let end = hex.find('}')?;
+                let char = char::from_u32(u32::from_str_radix(&hex[..end], 16).ok()?)?;
+                return Some(Unit {
+                    text: &data[..end + 4],
+                    char,
+                });
+            }
+            _ => return None,
+        };
+        Some(Unit { text: &data[..2], char })
+    }
+}
+impl<'a> Iterator for Units<'a> {
+    type Item = Unit<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let char = self.remaining.chars().next()?;
+        let unit = match self.escaped.then(|| Self::escape_sequence(self.remaining)).flatten() {
+            Some(unit) => unit,
+            None => Unit {
+                text: &self.remaining[..char.len_utf8()],
+                char,
+            },
+        };
+        self.remaining = &self.remaining[unit.text.len()..];
+        Some(unit)
+    }
+}
+
+/// Fills `columns` terminal columns with redaction characters, padding with spaces if the next one is too wide to fill
+/// them exactly.
+#[cfg(feature = "unicode-width")]
+fn write_columns(fmt: &mut dyn Write, mut columns: usize, redact_chars: &mut RedactChars) -> std::fmt::Result {
+    use unicode_width::UnicodeWidthChar;
+
+    while columns > 0 {
+        let char = redact_chars.next();
+
+        // Zero-width redaction characters would never fill anything, so treat them as a single column.
+        let char_width = char.width().unwrap_or(1).max(1);
+        if char_width > columns {
+            break;
+        }
+
+        fmt.write_char(char)?;
+        columns -= char_width;
+    }
+    for _ in 0..columns {
+        fmt.write_char(' ')?;
+    }
+    Ok(())
+}
+
+/// Reads the flags of a [`Redactor`](crate::redactor::Redactor) used with `#[redact(policy = ...)]`.
+#[inline(always)]
+pub const fn policy_flags(policy: &crate::redactor::Redactor) -> RedactFlags {
+    policy.flags
 }
 
 pub enum RedactionTarget<'a> {
@@ -116,14 +614,23 @@ pub enum RedactionTarget<'a> {
 
     /// Redact the output of the type's [`Display`] implementation.
     Display(&'a dyn Display),
+
+    /// Redact a string slice, such as a [`String`] or [`Cow<str>`](std::borrow::Cow), without formatting it first.
+    Str {
+        this: &'a str,
+
+        /// Whether to redact its [`Debug`] output rather than its [`Display`] output.
+        debug: bool,
+    },
 }
 impl RedactionTarget<'_> {
     /// Pass through directly to the formatter.
-    #[cfg(feature = "toggle")]
     pub(crate) fn passthrough(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
             RedactionTarget::Debug { this, .. } => std::fmt::Debug::fmt(this, fmt),
             RedactionTarget::Display(this) => std::fmt::Display::fmt(this, fmt),
+            RedactionTarget::Str { this, debug: true } => std::fmt::Debug::fmt(this, fmt),
+            RedactionTarget::Str { this, debug: false } => std::fmt::Display::fmt(this, fmt),
         }
     }
 }
@@ -134,6 +641,8 @@ impl Display for RedactionTarget<'_> {
             RedactionTarget::Debug { this, alternate: false } => write!(f, "{:?}", this),
             RedactionTarget::Debug { this, alternate: true } => write!(f, "{:#?}", this),
             RedactionTarget::Display(this) => write!(f, "{}", this),
+            RedactionTarget::Str { this, debug: true } => write!(f, "{:?}", this),
+            RedactionTarget::Str { this, debug: false } => f.write_str(this),
         }
     }
 }
@@ -145,50 +654,475 @@ pub struct RedactionFormatter<'a> {
 }
 impl std::fmt::Debug for RedactionFormatter<'_> {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_counting(fmt).map(drop)
+    }
+}
+impl RedactionFormatter<'_> {
+    /// Formats the redacted data like [`Debug`], returning how many of its characters were redacted.
+    pub(crate) fn fmt_counting(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<usize, std::fmt::Error> {
         #[cfg(feature = "toggle")]
-        if crate::toggle::get_redaction_behavior().is_plaintext() {
-            return self.this.passthrough(fmt);
+        if !self.flags.secret && crate::toggle::get_redaction_behavior().is_plaintext() {
+            return self.this.passthrough(fmt).map(|()| 0);
         }
 
+        let Some(padding) = &self.flags.pad else {
+            return self.fmt_unpadded(fmt);
+        };
+
+        let masked = std::cell::Cell::new(0);
+        let redacted = give_me_a_formatter(|fmt| self.fmt_unpadded(fmt).map(|count| masked.set(count)));
+        let redacted = if fmt.alternate() {
+            format!("{redacted:#}")
+        } else {
+            redacted.to_string()
+        };
+        padding.write(fmt, &redacted)?;
+        Ok(masked.get())
+    }
+
+    fn fmt_unpadded(&self, fmt: &mut std::fmt::Formatter<'_>) -> Result<usize, std::fmt::Error> {
         if let RedactionLength::Fixed(n) = &self.flags.redact_length {
-            return RedactFlags::redact_fixed(fmt, n.get() as usize, self.flags.redact_char);
+            self.flags.redact_fixed(fmt, n.get() as usize)?;
+
+            // The data is never formatted, so it's only counted when it's already a string.
+            return Ok(match self.this {
+                RedactionTarget::Str { this, .. } => {
+                    this.chars().filter(|char| self.flags.is_redactable(*char)).count()
+                }
+                _ => 0,
+            });
         }
 
-        let redactable_string = self.this.to_string();
+        // Strings are redacted straight from the borrowed data, unless their `Debug` output escapes something.
+        if let RedactionTarget::Str { this, debug } = self.this {
+            if !debug {
+                return self.redact_formatted(fmt, this, false);
+            } else if !this.chars().any(escapes_in_debug) {
+                fmt.write_char('"')?;
+                let masked = self.redact_formatted(fmt, this, false)?;
+                fmt.write_char('"')?;
+                return Ok(masked);
+            }
+        }
+
+        // Only `Debug` output escapes characters.
+        let escaped = matches!(
+            self.this,
+            RedactionTarget::Debug { .. } | RedactionTarget::Str { debug: true, .. }
+        );
+        self.redact_formatted(fmt, &self.this.to_string(), escaped)
+    }
+
+    fn redact_formatted(
+        &self,
+        fmt: &mut std::fmt::Formatter<'_>,
+        redactable_string: &str,
+        escaped: bool,
+    ) -> Result<usize, std::fmt::Error> {
+        // Empty output, e.g. of a unit-like wrapper, has nothing to redact and stays empty.
+        if redactable_string.is_empty() {
+            return Ok(0);
+        }
+
+        #[cfg(feature = "strict-mode")]
+        if crate::strict_mode::is_enabled() {
+            self.assert_redacted(redactable_string, escaped);
+        }
 
-        #[allow(clippy::single_match)]
+        // `{:#?}` output of the Debug target is already indented relative to itself, unlike the Display target.
+        if fmt.alternate() && !escaped && redactable_string.contains('\n') {
+            self.redact_str(&mut IndentContinuationLines(fmt), redactable_string, escaped)
+        } else {
+            self.redact_str(fmt, redactable_string, escaped)
+        }
+    }
+
+    /// Panics if redacting the data produces the original data, under [`strict_mode`](crate::strict_mode).
+    #[cfg(feature = "strict-mode")]
+    fn assert_redacted(&self, redactable_string: &str, escaped: bool) {
+        if matches!(self.specialization, Some(RedactSpecialization::Option)) && redactable_string == "None" {
+            return;
+        }
+
+        let mut redacted = String::with_capacity(redactable_string.len());
+        if self.redact_str(&mut redacted, redactable_string, escaped).is_ok()
+            && redacted == redactable_string
+            && redactable_string.chars().any(|char| self.flags.is_redactable(char))
+        {
+            panic!(
+                "veil strict mode: redacting a value produced the original value, check its `#[redact(...)]` modifiers"
+            );
+        }
+    }
+
+    /// Redacts the data, returning how many of its characters were redacted.
+    fn redact_str(
+        &self,
+        fmt: &mut dyn Write,
+        redactable_string: &str,
+        escaped: bool,
+    ) -> Result<usize, std::fmt::Error> {
+        let mut redact_chars = self.flags.redact_chars();
         match self.specialization {
             Some(RedactSpecialization::Option) => {
                 if redactable_string == "None" {
                     // We don't need to do any redacting
                     // https://prima.slack.com/archives/C03URH9N43U/p1661423554871499
-                    return fmt.write_str("None");
+                    fmt.write_str("None")?;
                 } else if let Some(inner) = redactable_string
                     .strip_prefix("Some(")
                     .and_then(|inner| inner.strip_suffix(')'))
                 {
                     fmt.write_str("Some(")?;
-                    if let RedactionLength::Partial = &self.flags.redact_length {
-                        self.flags.redact_partial(fmt, inner)?;
-                    } else {
-                        self.flags.redact_full(fmt, inner)?;
-                    }
-                    return fmt.write_char(')');
+                    self.redact_units(fmt, Units::new(inner, escaped), &mut redact_chars)?;
+                    fmt.write_char(')')?;
                 } else {
                     // This should never happen, but just in case...
-                    return self.flags.redact_full(fmt, &redactable_string);
+                    self.flags
+                        .redact_full(fmt, Units::new(redactable_string, escaped), &mut redact_chars)?;
+                }
+                return Ok(redact_chars.masked);
+            }
+
+            Some(RedactSpecialization::Vec) => {
+                if let Some(inner) = redactable_string
+                    .strip_prefix('[')
+                    .and_then(|inner| inner.strip_suffix(']'))
+                {
+                    // Everything between the elements, such as `, ` or the indentation of `{:#?}`, is kept as-is.
+                    fmt.write_char('[')?;
+                    let mut written = 0;
+                    for element in list_elements(inner, is_element_separator) {
+                        fmt.write_str(&inner[written..element.start])?;
+                        self.redact_units(fmt, Units::new(&inner[element.clone()], escaped), &mut redact_chars)?;
+                        written = element.end;
+                    }
+                    fmt.write_str(&inner[written..])?;
+                    fmt.write_char(']')?;
+                    return Ok(redact_chars.masked);
                 }
             }
 
-            None => {}
+            Some(RedactSpecialization::Map) => {
+                if let Some(inner) = redactable_string
+                    .strip_prefix('{')
+                    .and_then(|inner| inner.strip_suffix('}'))
+                {
+                    fmt.write_char('{')?;
+                    let mut written = 0;
+                    for entry in list_elements(inner, is_element_separator) {
+                        // Anything after the first `: ` is part of the value, even if it contains another one.
+                        let parts = list_elements(&inner[entry.clone()], is_key_separator);
+                        let value = match (parts.get(1), parts.last()) {
+                            (Some(first), Some(last)) => entry.start + first.start..entry.start + last.end,
+                            _ => entry,
+                        };
+                        fmt.write_str(&inner[written..value.start])?;
+                        self.redact_units(fmt, Units::new(&inner[value.clone()], escaped), &mut redact_chars)?;
+                        written = value.end;
+                    }
+                    fmt.write_str(&inner[written..])?;
+                    fmt.write_char('}')?;
+                    return Ok(redact_chars.masked);
+                }
+            }
+
+            // `Display` output of an error is just its message, so there's no type name to keep.
+            Some(RedactSpecialization::Error) if escaped => {
+                if let Some((ty, message)) = split_error_type(redactable_string) {
+                    fmt.write_str(ty)?;
+                    self.redact_units(fmt, Units::new(message, escaped), &mut redact_chars)?;
+                    return Ok(redact_chars.masked);
+                }
+            }
+
+            _ => {}
         }
 
-        if let RedactionLength::Partial = &self.flags.redact_length {
-            self.flags.redact_partial(fmt, &redactable_string)
-        } else {
-            self.flags.redact_full(fmt, &redactable_string)
+        self.redact_units(fmt, Units::new(redactable_string, escaped), &mut redact_chars)?;
+        Ok(redact_chars.masked)
+    }
+
+    fn redact_units(&self, fmt: &mut dyn Write, units: Units, redact_chars: &mut RedactChars) -> std::fmt::Result {
+        #[cfg(feature = "hash")]
+        if let Some(algo) = self.flags.hash {
+            return self.flags.redact_hash(fmt, units, redact_chars, algo);
+        }
+
+        match self.flags.redact_length {
+            RedactionLength::Partial | RedactionLength::PartialPercent(_) | RedactionLength::PartialExpose(_) => {
+                self.flags.redact_partial(fmt, units, redact_chars)
+            }
+            RedactionLength::MaskEnds { prefix, suffix } => {
+                self.flags
+                    .redact_ends(fmt, units, redact_chars, prefix as usize, suffix as usize)
+            }
+            RedactionLength::Full | RedactionLength::Fixed(_) => self.flags.redact_full(fmt, units, redact_chars),
+        }
+    }
+}
+
+/// Returns whether a character is escaped in the [`Debug`] output of a string.
+fn escapes_in_debug(char: char) -> bool {
+    // Unlike `char`'s, `str`'s `Debug` output doesn't escape single quotes.
+    char != '\'' && char.escape_debug().len() != 1
+}
+
+/// Splits the name of the error type off the start of an error's [`Debug`] output, e.g. `ParseError` off
+/// `ParseError("...")` or `Custom` off `Custom { kind: Other, error: "..." }`.
+///
+/// Returns [`None`] if the output doesn't start with a type name, such as the quoted message of a `Box<dyn Error>`
+/// created from a string, as any leading word could be part of the message.
+fn split_error_type(debug: &str) -> Option<(&str, &str)> {
+    let end = debug.find(|char: char| !(char.is_alphanumeric() || char == '_' || char == ':'))?;
+    let (ty, message) = debug.split_at(end);
+    if !ty.starts_with(|char: char| char.is_alphabetic() || char == '_') {
+        return None;
+    }
+    (message.starts_with('(') || message.starts_with(" {")).then_some((ty, message))
+}
+
+/// Finds the elements of a list's [`Debug`] output between its brackets, e.g. `"a, b", ["c"]`, returning where each
+/// of them starts and ends, without the whitespace around them.
+///
+/// Elements are separated where `is_separator` returns `true` for the rest of the data, which is only checked outside
+/// of any brackets, parentheses, braces and string or `char` literals. The separator's first character is skipped.
+fn list_elements(inner: &str, is_separator: fn(&str) -> bool) -> Vec<std::ops::Range<usize>> {
+    let mut elements = Vec::new();
+    let mut element: Option<std::ops::Range<usize>> = None;
+    let mut depth = 0usize;
+    let mut literal: Option<char> = None;
+    let mut escaped = false;
+    for (i, char) in inner.char_indices() {
+        if let Some(quote) = literal {
+            if escaped {
+                escaped = false;
+            } else if char == '\\' {
+                escaped = true;
+            } else if char == quote {
+                literal = None;
+            }
+        } else if depth == 0 && is_separator(&inner[i..]) {
+            elements.extend(element.take());
+            continue;
+        } else if char == '"' {
+            literal = Some(char);
+        } else if char == '\'' && is_char_literal(&inner[i..]) {
+            // Apostrophes that aren't the start of a `char` literal, e.g. in an element's `Debug` output of `don't`,
+            // are left alone.
+            literal = Some(char);
+        } else if matches!(char, '[' | '(' | '{') {
+            depth += 1;
+        } else if matches!(char, ']' | ')' | '}') {
+            depth = depth.saturating_sub(1);
+        } else if char.is_whitespace() {
+            continue;
+        }
+
+        let end = i + char.len_utf8();
+        match &mut element {
+            Some(element) => element.end = end,
+            None => element = Some(i..end),
         }
     }
+    elements.extend(element);
+    elements
+}
+
+/// Separates the elements of a list, or the entries of a map.
+fn is_element_separator(data: &str) -> bool {
+    data.starts_with(',')
+}
+
+/// Separates the key of a map's entry from its value, e.g. `"name": "John"`, but not the segments of a path such as
+/// `Kind::Name`.
+fn is_key_separator(data: &str) -> bool {
+    data.strip_prefix(':')
+        .is_some_and(|rest| rest.starts_with(char::is_whitespace))
+}
+
+/// Returns whether the data starts with a `char` literal, e.g. `'a'` or `'\n'`.
+fn is_char_literal(data: &str) -> bool {
+    let mut chars = data.chars().skip(1);
+    matches!((chars.next(), chars.next()), (Some('\\'), _) | (Some(_), Some('\'')))
+}
+
+/// Redacts a field with a `#[redact(with_fn_ctx = ...)]` function, which receives the field's name, its formatted
+/// value and a [`Redactor`](crate::redactor::Redactor) with the field's configured flags.
+pub struct RedactionFnFormatter<'a> {
+    pub this: RedactionTarget<'a>,
+    pub field: &'static str,
+    pub flags: RedactFlags,
+    pub redact: fn(&str, &str, &crate::redactor::Redactor) -> String,
+}
+impl std::fmt::Debug for RedactionFnFormatter<'_> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        #[cfg(feature = "toggle")]
+        if !self.flags.secret && crate::toggle::get_redaction_behavior().is_plaintext() {
+            return self.this.passthrough(fmt);
+        }
+
+        let value = self.this.to_string();
+        fmt.write_str(&(self.redact)(
+            self.field,
+            &value,
+            &crate::redactor::Redactor {
+                flags: self.flags,
+                when: None,
+            },
+        ))
+    }
+}
+
+/// Formats the target as-is, used in place of every redacted field under the `redact-noop` feature.
+pub struct PassthroughFormatter<'a>(pub RedactionTarget<'a>);
+impl std::fmt::Debug for PassthroughFormatter<'_> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.passthrough(fmt)
+    }
+}
+
+/// Shows a redacted function pointer or closure as `<fn>`, without requiring it to implement [`Debug`].
+pub struct FnMarker<'a, T: ?Sized>(pub &'a T);
+impl<T: ?Sized> std::fmt::Debug for FnMarker<'_, T> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.write_str("<fn>")
+    }
+}
+
+/// The marker a `#[redact(opaque)]` struct is shown with in place of all of its fields, e.g. the `*` of `CreditCard(*)`.
+///
+/// There is no data to show instead, so the marker is shown even when redaction is disabled.
+pub struct OpaqueMarker(pub RedactFlags);
+impl std::fmt::Debug for OpaqueMarker {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = match self.0.redact_length {
+            RedactionLength::Fixed(width) => width.get(),
+            _ => 1,
+        };
+        self.0.redact_fixed(fmt, width as usize)
+    }
+}
+
+/// Shows the type of a `#[redact(type_only)]` field instead of its value, e.g. `<String>`.
+pub struct TypeOnlyFormatter<'a> {
+    #[cfg_attr(not(feature = "toggle"), allow(dead_code))]
+    pub this: &'a dyn Debug,
+    pub ty: &'static str,
+}
+impl std::fmt::Debug for TypeOnlyFormatter<'_> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        #[cfg(feature = "toggle")]
+        if crate::toggle::get_redaction_behavior().is_plaintext() {
+            return Debug::fmt(self.this, fmt);
+        }
+
+        write!(fmt, "<{}>", self.ty)
+    }
+}
+
+/// Formats the inner value, but writes `<unprintable>` instead of propagating a panic from its formatting.
+///
+/// Used by `#[redact(catch)]`. Anything the inner value wrote before panicking is kept, so it should only wrap a value
+/// that formats its data into a buffer before writing anything, like [`RedactionFormatter`] and
+/// [`RedactionFnFormatter`] do, unless redaction is disabled with the `toggle` feature.
+pub struct CatchUnwind<'a>(pub &'a dyn Debug);
+impl Debug for CatchUnwind<'_> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| Debug::fmt(self.0, fmt))) {
+            Ok(result) => result,
+            Err(_) => fmt.write_str("<unprintable>"),
+        }
+    }
+}
+
+/// The result of a `#[redact(try_display = "...")]` method, formatted as its `Ok` value or `<unformattable>` if it
+/// failed, so that a failing field doesn't fail the rest of the output with it.
+///
+/// The output is redacted like any other [`Display`] output, including the placeholder.
+pub struct TryDisplay<T, E>(pub Result<T, E>);
+impl<T: Display, E> Display for TryDisplay<T, E> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Ok(value) => Display::fmt(value, fmt),
+            Err(_) => fmt.write_str("<unformattable>"),
+        }
+    }
+}
+
+/// Counts how deeply a `#[redact(max_depth = N)]` type is nested within its own [`Debug`] output on the current thread,
+/// leaving a level when dropped.
+pub struct DepthGuard(&'static std::thread::LocalKey<std::cell::Cell<usize>>);
+impl DepthGuard {
+    /// Enters one more level, or returns `None` without entering it if that would be deeper than `max_depth`.
+    pub fn enter(depth: &'static std::thread::LocalKey<std::cell::Cell<usize>>, max_depth: usize) -> Option<Self> {
+        let entered = depth.with(|depth| {
+            let entered = depth.get() < max_depth;
+            if entered {
+                depth.set(depth.get() + 1);
+            }
+            entered
+        });
+        // Not `then_some`, which would drop a guard for a level that was never entered.
+        entered.then(|| Self(depth))
+    }
+}
+impl Drop for DepthGuard {
+    fn drop(&mut self) {
+        self.0.with(|depth| depth.set(depth.get() - 1));
+    }
+}
+
+/// Under `{:#?}`, the standard library already indents every line of a field's value to the depth of the field itself.
+///
+/// This writes through to the formatter and indents every line after the first by one more level, so that multi-line
+/// redacted values are nested under their field like any other multi-line value.
+struct IndentContinuationLines<'a, 'b>(&'a mut std::fmt::Formatter<'b>);
+impl Write for IndentContinuationLines<'_, '_> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        for (i, line) in s.split('\n').enumerate() {
+            if i > 0 {
+                self.0.write_str("\n    ")?;
+            }
+            self.0.write_str(line)?;
+        }
+        Ok(())
+    }
+}
+
+/// Like [`RedactionFormatter`] with a [`RedactionTarget::Debug`] target, but reads whether to use the
+/// "alternate" format from the formatter at the time of formatting.
+///
+/// Used by `#[redact_impl]`, where the formatter is usually mutably borrowed by a builder.
+pub struct RedactionDebugFormatter<'a> {
+    pub this: &'a dyn Debug,
+    pub flags: RedactFlags,
+    pub specialization: Option<RedactSpecialization>,
+}
+impl std::fmt::Debug for RedactionDebugFormatter<'_> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(
+            &RedactionFormatter {
+                this: RedactionTarget::Debug {
+                    this: self.this,
+                    alternate: fmt.alternate(),
+                },
+                flags: self.flags,
+                specialization: self.specialization,
+            },
+            fmt,
+        )
+    }
+}
+
+/// Formats a value as [`Debug`] using its own [`Redactable`](crate::Redactable) implementation.
+///
+/// Used by `#[redact(redactable)]`.
+pub struct RedactableFormatter<'a, T: crate::Redactable + ?Sized>(pub &'a T);
+impl<T: crate::Redactable + ?Sized> std::fmt::Debug for RedactableFormatter<'_, T> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.redact_into(fmt)
+    }
 }
 
 pub fn derived_redactable(this: &dyn Display, flags: RedactFlags) -> String {
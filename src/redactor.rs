@@ -1,12 +1,20 @@
 //! The [`Redactor`] allows for redacting arbitrary strings using a pre-defined set of flags.
 //!
-//! To build a [`Redactor`], use the [`RedactorBuilder`].
+//! To build a [`Redactor`], use the [`RedactorBuilder`], or parse it from the same modifiers as `#[redact(...)]`.
 
+pub use crate::private::RedactionLength;
 use crate::{
-    private::{RedactFlags, RedactionFormatter, RedactionLength, RedactionTarget},
+    private::{ExposeSide, Padding, PreserveClass, RedactFlags, RedactionFormatter, RedactionTarget},
     util::give_me_a_formatter,
 };
-use std::fmt::{Debug, Display};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt::{Debug, Display, Write},
+    hash::Hash,
+    num::NonZeroU8,
+    str::FromStr,
+};
 
 /// A wrapped reference to some data that, when formatted as [`Debug`] or [`Display`] (if implemented for `T`), will be redacted.
 ///
@@ -14,17 +22,24 @@ use std::fmt::{Debug, Display};
 #[derive(Clone, Copy)]
 pub struct RedactWrapped<'a, T> {
     data: &'a T,
-    flags: &'a RedactFlags,
+    redactor: &'a Redactor,
 }
 impl<T> Display for RedactWrapped<'_, T>
 where
     T: Display,
 {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(when) = self.redactor.when {
+            let data = self.data.to_string();
+            if !when(&data) {
+                return fmt.write_str(&data);
+            }
+        }
+
         std::fmt::Debug::fmt(
             &RedactionFormatter {
                 this: RedactionTarget::Display(self.data),
-                flags: *self.flags,
+                flags: self.redactor.flags,
                 specialization: None,
             },
             fmt,
@@ -36,13 +51,24 @@ where
     T: Debug,
 {
     fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(when) = self.redactor.when {
+            let data = if fmt.alternate() {
+                format!("{:#?}", self.data)
+            } else {
+                format!("{:?}", self.data)
+            };
+            if !when(&data) {
+                return fmt.write_str(&data);
+            }
+        }
+
         std::fmt::Debug::fmt(
             &RedactionFormatter {
                 this: RedactionTarget::Debug {
                     this: self.data,
                     alternate: fmt.alternate(),
                 },
-                flags: *self.flags,
+                flags: self.redactor.flags,
                 specialization: None,
             },
             fmt,
@@ -50,10 +76,71 @@ where
     }
 }
 
+/// Writes into a fixed-size buffer, failing once it's full.
+struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+impl std::fmt::Write for SliceWriter<'_> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        let end = self.len + s.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(std::fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Compares everything written against the given data, only allocating from the first difference onwards.
+struct CowWriter<'a> {
+    data: &'a str,
+
+    /// How many bytes of the data have been written unchanged.
+    unchanged: usize,
+
+    /// Everything written so far, once it differs from the data.
+    changed: Option<String>,
+}
+impl<'a> CowWriter<'a> {
+    fn finish(self) -> Cow<'a, str> {
+        match self.changed {
+            Some(changed) => Cow::Owned(changed),
+            None if self.unchanged == self.data.len() => Cow::Borrowed(self.data),
+            None => Cow::Owned(self.data[..self.unchanged].to_string()),
+        }
+    }
+}
+impl std::fmt::Write for CowWriter<'_> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        if let Some(changed) = &mut self.changed {
+            changed.push_str(s);
+        } else if self.data[self.unchanged..].starts_with(s) {
+            self.unchanged += s.len();
+        } else {
+            let mut changed = String::with_capacity(self.data.len());
+            changed.push_str(&self.data[..self.unchanged]);
+            changed.push_str(s);
+            self.changed = Some(changed);
+        }
+        Ok(())
+    }
+}
+
 /// The `Redactor` allows for redacting arbitrary strings using a pre-defined set of flags.
 ///
 /// To build a `Redactor`, use the [`RedactorBuilder`].
-pub struct Redactor(RedactFlags);
+///
+/// With the `toggle` feature, a `Redactor` returns its input unredacted if redaction has been [disabled](crate::disable),
+/// the same as `#[derive(Redact)]`. Don't rely on it to scrub data that must never be stored or sent elsewhere in plaintext
+/// if redaction may be disabled in that environment.
+pub struct Redactor {
+    pub(crate) flags: RedactFlags,
+
+    /// Only data matching this predicate is redacted, see [`RedactorBuilder::when`].
+    pub(crate) when: Option<fn(&str) -> bool>,
+}
 impl Redactor {
     /// Returns a builder ([`RedactorBuilder`]) for this type.
     #[inline(always)]
@@ -61,6 +148,91 @@ impl Redactor {
         RedactorBuilder::new()
     }
 
+    /// Builds a `Redactor` from environment variables starting with the given prefix, so that redaction can be
+    /// configured per deployment.
+    ///
+    /// The following variables are read, e.g. with the prefix `REDACT`:
+    ///
+    /// * `REDACT_MODE`: `full`, or modifiers in the same format as [`FromStr`](#impl-FromStr-for-Redactor), e.g.
+    ///   `partial` or `fixed = 3`. Defaults to `full`.
+    /// * `REDACT_CHAR`: the character to redact with, e.g. `#`. Defaults to `*`, or the default of the enabled
+    ///   [redaction profile](crate#redaction-profiles).
+    ///
+    /// Returns an error if either variable is set to an invalid value.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use veil::redactor::Redactor;
+    /// std::env::set_var("REDACT_MODE", "partial");
+    /// std::env::set_var("REDACT_CHAR", "X");
+    ///
+    /// let redactor = Redactor::from_env("REDACT").unwrap();
+    ///
+    /// assert_eq!(redactor.redact("john.doe@prima.it".to_string()), "johX.XXX@XXXXa.it");
+    /// ```
+    pub fn from_env(prefix: &str) -> Result<Self, &'static str> {
+        let var = |name: &str| match std::env::var(format!("{prefix}_{name}")) {
+            Ok(value) => Ok(Some(value)),
+            Err(std::env::VarError::NotPresent) => Ok(None),
+            Err(std::env::VarError::NotUnicode(_)) => Err("redaction environment variables must be valid unicode"),
+        };
+
+        let mut spec = match var("MODE")? {
+            Some(mode) if mode.trim() == "full" => String::new(),
+            Some(mode) if parse_modifiers(&mode).any(|(name, _)| name == "with") => {
+                return Err("the `_MODE` environment variable can't set `with`, use the `_CHAR` variable instead")
+            }
+            Some(mode) => mode,
+            None => String::new(),
+        };
+        if let Some(char) = var("CHAR")? {
+            let mut chars = char.chars();
+            let (Some(char), None) = (chars.next(), chars.next()) else {
+                return Err("the `_CHAR` environment variable must be a single character");
+            };
+            spec.push_str(&format!(", with = '{char}'"));
+        }
+
+        spec.parse()
+    }
+
+    /// Returns the character this `Redactor` redacts with.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use veil::redactor::Redactor;
+    /// let redactor = Redactor::builder().char('X').build().unwrap();
+    ///
+    /// assert_eq!(redactor.mask_char(), 'X');
+    /// ```
+    #[inline(always)]
+    pub const fn mask_char(&self) -> char {
+        self.flags.redact_char
+    }
+
+    /// Returns how much of the data this `Redactor` redacts.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use veil::redactor::{RedactionLength, Redactor};
+    /// let redactor = Redactor::builder().partial().build().unwrap();
+    ///
+    /// assert_eq!(redactor.mask_mode(), RedactionLength::Partial);
+    /// ```
+    #[inline(always)]
+    pub const fn mask_mode(&self) -> RedactionLength {
+        self.flags.redact_length
+    }
+
+    /// Returns whether this `Redactor` only redacts digits, see `#[redact(digits_only)]`.
+    #[inline(always)]
+    pub const fn digits_only(&self) -> bool {
+        self.flags.digits_only
+    }
+
     /// Redact the given string.
     ///
     /// # Example
@@ -81,11 +253,204 @@ impl Redactor {
     /// );
     /// ```
     pub fn redact(&self, data: String) -> String {
+        if self.when.is_some_and(|when| !when(&data)) {
+            return data;
+        }
+
         give_me_a_formatter(|fmt| {
             std::fmt::Debug::fmt(
                 &RedactionFormatter {
                     this: RedactionTarget::Display(&data.as_str()),
-                    flags: self.0,
+                    flags: self.flags,
+                    specialization: None,
+                },
+                fmt,
+            )
+        })
+        .to_string()
+    }
+
+    /// Redact the given string, also returning how many of its characters were redacted, e.g. for metrics on how much
+    /// data is being redacted.
+    ///
+    /// Characters exposed by [`partial`](RedactorBuilder::partial) redaction aren't counted, and neither are the extra
+    /// redaction characters added by [`min_mask_len`](RedactorBuilder::min_mask_len). Data that doesn't match the
+    /// [`when`](RedactorBuilder::when) predicate is returned as-is, with a count of zero.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use veil::redactor::Redactor;
+    /// let redactor = Redactor::builder().partial().build().unwrap();
+    ///
+    /// let (email, masked) = redactor.redact_counting("john.doe@prima.it");
+    ///
+    /// assert_eq!(email, "joh*.***@****a.it");
+    /// assert_eq!(masked, 8);
+    /// ```
+    pub fn redact_counting(&self, data: &str) -> (String, usize) {
+        if self.when.is_some_and(|when| !when(data)) {
+            return (data.to_string(), 0);
+        }
+
+        let masked = std::cell::Cell::new(0);
+        let redacted = give_me_a_formatter(|fmt| {
+            RedactionFormatter {
+                this: RedactionTarget::Str {
+                    this: data,
+                    debug: false,
+                },
+                flags: self.flags,
+                specialization: None,
+            }
+            .fmt_counting(fmt)
+            .map(|count| masked.set(count))
+        })
+        .to_string();
+        (redacted, masked.get())
+    }
+
+    /// Redact the given string, reusing its allocation if it has enough spare capacity for the redacted output.
+    ///
+    /// The redacted output is written after the data and then moved to the front, so the data's capacity must be at
+    /// least its length plus the length of the redacted output, e.g. because it's a buffer that's reused for many
+    /// values. Otherwise, this allocates a new string like [`redact`](Redactor::redact).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use veil::redactor::Redactor;
+    /// let redactor = Redactor::builder().partial().build().unwrap();
+    ///
+    /// let mut email = String::with_capacity(64);
+    /// email.push_str("john.doe@prima.it");
+    ///
+    /// let buffer = email.as_ptr();
+    /// let email = redactor.redact_owned(email);
+    ///
+    /// assert_eq!(email, "joh*.***@****a.it");
+    /// assert_eq!(email.as_ptr(), buffer);
+    /// ```
+    pub fn redact_owned(&self, data: String) -> String {
+        if self.when.is_some_and(|when| !when(&data)) || data.len() == data.capacity() {
+            return self.redact(data);
+        }
+
+        let len = data.len();
+        let mut bytes = data.into_bytes();
+
+        // Initializing the spare capacity never reallocates.
+        bytes.resize(bytes.capacity(), 0);
+
+        let (data, spare) = bytes.split_at_mut(len);
+        let data = std::str::from_utf8(data).expect("a `String` is always valid UTF-8");
+        let mut redacted = SliceWriter { buf: spare, len: 0 };
+        let fits = write!(
+            redacted,
+            "{:?}",
+            RedactionFormatter {
+                this: RedactionTarget::Display(&data),
+                flags: self.flags,
+                specialization: None,
+            }
+        )
+        .is_ok();
+        let redacted_len = redacted.len;
+
+        if fits {
+            bytes.copy_within(len..len + redacted_len, 0);
+            bytes.truncate(redacted_len);
+        } else {
+            bytes.truncate(len);
+        }
+
+        let data = String::from_utf8(bytes).expect("redacted output is always valid UTF-8");
+        if fits {
+            data
+        } else {
+            self.redact(data)
+        }
+    }
+
+    /// Redact the given string, only allocating if redacting changes it.
+    ///
+    /// Borrowed data that redaction leaves as-is, e.g. because it has no characters to redact or doesn't match the
+    /// [`when`](RedactorBuilder::when) predicate, is returned borrowed. Owned data is returned as-is if it's unchanged, and
+    /// otherwise redacted like [`redact_owned`](Redactor::redact_owned), reusing its allocation if it has enough spare
+    /// capacity.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use veil::redactor::Redactor;
+    /// # use std::borrow::Cow;
+    /// let redactor = Redactor::builder().partial().build().unwrap();
+    ///
+    /// assert!(matches!(redactor.redact_borrowed("--:--"), Cow::Borrowed("--:--")));
+    /// assert_eq!(redactor.redact_borrowed("john.doe@prima.it"), "joh*.***@****a.it");
+    /// assert_eq!(redactor.redact_borrowed("John Doe".to_string()), "Jo** *oe");
+    /// ```
+    pub fn redact_borrowed<'a>(&self, data: impl Into<Cow<'a, str>>) -> Cow<'a, str> {
+        let data = match data.into() {
+            Cow::Borrowed(data) => data,
+            Cow::Owned(data) if data.len() < data.capacity() => return Cow::Owned(self.redact_owned(data)),
+            Cow::Owned(data) => {
+                // Without spare capacity, owned data is only kept if it's unchanged.
+                return match self.redact_borrowed(data.as_str()) {
+                    Cow::Borrowed(_) => Cow::Owned(data),
+                    Cow::Owned(redacted) => Cow::Owned(redacted),
+                };
+            }
+        };
+        if self.when.is_some_and(|when| !when(data)) {
+            return Cow::Borrowed(data);
+        }
+
+        let mut redacted = CowWriter {
+            data,
+            unchanged: 0,
+            changed: None,
+        };
+        write!(
+            redacted,
+            "{:?}",
+            RedactionFormatter {
+                this: RedactionTarget::Str {
+                    this: data,
+                    debug: false
+                },
+                flags: self.flags,
+                specialization: None,
+            }
+        )
+        .expect("redacting a string never fails");
+        redacted.finish()
+    }
+
+    /// Redact the [`Display`] output of the given value.
+    ///
+    /// Equivalent to `redactor.redact(value.to_string())`, but formats and redacts in one step.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use veil::redactor::Redactor;
+    /// let account_number: u64 = 1234567890;
+    ///
+    /// let redactor = Redactor::builder().partial().build().unwrap();
+    ///
+    /// assert_eq!(redactor.redact_display(&account_number), "123****890");
+    /// ```
+    pub fn redact_display<T: Display>(&self, value: &T) -> String {
+        if self.when.is_some() {
+            return self.redact(value.to_string());
+        }
+
+        give_me_a_formatter(|fmt| {
+            std::fmt::Debug::fmt(
+                &RedactionFormatter {
+                    this: RedactionTarget::Display(value),
+                    flags: self.flags,
                     specialization: None,
                 },
                 fmt,
@@ -94,6 +459,201 @@ impl Redactor {
         .to_string()
     }
 
+    /// Redact all string values in the given JSON document.
+    ///
+    /// Object keys, numbers, booleans and nulls are left as-is. Nested objects and arrays are redacted recursively.
+    /// The order of object keys may not be preserved, unless `serde_json`'s `preserve_order` feature is enabled.
+    ///
+    /// Returns an error if the given string is not valid JSON.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use veil::redactor::Redactor;
+    /// let body = r#"{"age":30,"emails":["john.doe@prima.it"],"name":"John Doe"}"#;
+    ///
+    /// let redactor = Redactor::builder().build().unwrap();
+    ///
+    /// assert_eq!(
+    ///     redactor.redact_json_str(body).unwrap(),
+    ///     r#"{"age":30,"emails":["****.***@*****.**"],"name":"**** ***"}"#
+    /// );
+    /// ```
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn redact_json_str(&self, json: &str) -> Result<String, serde_json::Error> {
+        let mut value: serde_json::Value = serde_json::from_str(json)?;
+        self.redact_json_value(&mut value);
+        serde_json::to_string(&value)
+    }
+
+    #[cfg(feature = "json")]
+    fn redact_json_value(&self, value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::String(string) => {
+                self.redact_in_place(string);
+            }
+            serde_json::Value::Array(array) => array.iter_mut().for_each(|value| self.redact_json_value(value)),
+            serde_json::Value::Object(object) => object.values_mut().for_each(|value| self.redact_json_value(value)),
+            serde_json::Value::Null | serde_json::Value::Bool(_) | serde_json::Value::Number(_) => {}
+        }
+    }
+
+    /// Redact the values of the given keys in a URL's query string, leaving everything else as-is.
+    ///
+    /// Accepts either a full URL or just its query string, with or without the leading `?`. If there's no `?`, the
+    /// whole string is treated as a query string. Any `#fragment` is left as-is.
+    ///
+    /// Keys are matched exactly, without percent-decoding them. Values are redacted as they're written, so
+    /// percent-encoded characters such as `%40` keep their `%` and redact their hex digits, e.g. `%**`.
+    ///
+    /// The [`when`](RedactorBuilder::when) predicate is called once with the whole URL or query string, not with each
+    /// value. If it doesn't match, everything is returned as-is.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use veil::redactor::Redactor;
+    /// let redactor = Redactor::builder().build().unwrap();
+    ///
+    /// assert_eq!(
+    ///     redactor.redact_query_string("https://prima.it/login?token=abc123&user=jdoe&lang=en", &["token", "user"]),
+    ///     "https://prima.it/login?token=******&user=****&lang=en"
+    /// );
+    /// ```
+    pub fn redact_query_string(&self, url_or_query: &str, keys: &[&str]) -> String {
+        let Some(redactor) = self.unconditional(url_or_query) else {
+            return url_or_query.to_string();
+        };
+
+        let (prefix, query) = match url_or_query.split_once('?') {
+            Some((prefix, query)) => (Some(prefix), query),
+            None => (None, url_or_query),
+        };
+        let (query, fragment) = match query.split_once('#') {
+            Some((query, fragment)) => (query, Some(fragment)),
+            None => (query, None),
+        };
+
+        let mut redacted = String::with_capacity(url_or_query.len());
+        if let Some(prefix) = prefix {
+            redacted.push_str(prefix);
+            redacted.push('?');
+        }
+        for (i, param) in query.split('&').enumerate() {
+            if i > 0 {
+                redacted.push('&');
+            }
+            match param.split_once('=') {
+                Some((key, value)) if keys.contains(&key) => {
+                    redacted.push_str(key);
+                    redacted.push('=');
+                    redacted.push_str(&redactor.redact(value.to_string()));
+                }
+                _ => redacted.push_str(param),
+            }
+        }
+        if let Some(fragment) = fragment {
+            redacted.push('#');
+            redacted.push_str(fragment);
+        }
+        redacted
+    }
+
+    /// Redact the directory and file names of a filesystem path, leaving its separators and the file's extension as-is,
+    /// e.g. to log the shape of a path without the names in it.
+    ///
+    /// Both `/` and `\` are treated as separators. Each name is redacted like with [`redact`](Redactor::redact), and
+    /// `.` and `..` are left as-is. Only the last component's extension is kept, and a leading dot, as in `.bashrc`,
+    /// doesn't start an extension.
+    ///
+    /// The [`when`](RedactorBuilder::when) predicate is called once with the whole path, not with each name. If it
+    /// doesn't match, the path is returned as-is.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use veil::redactor::Redactor;
+    /// let redactor = Redactor::builder().build().unwrap();
+    ///
+    /// assert_eq!(redactor.redact_path("/home/jdoe/invoices/2024.pdf"), "/****/****/********/****.pdf");
+    /// assert_eq!(redactor.redact_path(r"C:\Users\jdoe\notes"), r"*:\*****\****\*****");
+    /// ```
+    pub fn redact_path(&self, path: &str) -> String {
+        let Some(redactor) = self.unconditional(path) else {
+            return path.to_string();
+        };
+
+        let mut redacted = String::with_capacity(path.len());
+        for component in path.split_inclusive(['/', '\\']) {
+            let (name, separator) = match component.strip_suffix(['/', '\\']) {
+                Some(name) => (name, &component[name.len()..]),
+                None => (component, ""),
+            };
+            match name {
+                "" | "." | ".." => redacted.push_str(name),
+                // Only the last component, which has no separator after it, is a file name that can have an extension.
+                _ if separator.is_empty() => match name.rsplit_once('.') {
+                    Some((stem, extension)) if !stem.is_empty() => {
+                        redacted.push_str(&redactor.redact(stem.to_string()));
+                        redacted.push('.');
+                        redacted.push_str(extension);
+                    }
+                    _ => redacted.push_str(&redactor.redact(name.to_string())),
+                },
+                _ => redacted.push_str(&redactor.redact(name.to_string())),
+            }
+            redacted.push_str(separator);
+        }
+        redacted
+    }
+
+    /// Redact the local part of an email address, the part before the `@`, leaving the domain as-is.
+    ///
+    /// The local part is redacted like with [`redact`](Redactor::redact), including any `+tag` subaddress, as that
+    /// can identify someone as well. Only the last `@` separates the domain, as quoted local parts may contain `@`
+    /// themselves. Data without an `@` isn't a valid email address, so it's redacted entirely.
+    ///
+    /// The [`when`](RedactorBuilder::when) predicate is called once with the whole address, not with its local part. If
+    /// it doesn't match, the address is returned as-is.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use veil::redactor::Redactor;
+    /// let redactor = Redactor::builder().build().unwrap();
+    ///
+    /// assert_eq!(redactor.redact_email("john.doe+invoices@prima.it"), "****.***+********@prima.it");
+    /// assert_eq!(redactor.redact_email("john.doe"), "****.***");
+    /// ```
+    pub fn redact_email(&self, email: &str) -> String {
+        let Some(redactor) = self.unconditional(email) else {
+            return email.to_string();
+        };
+
+        match email.rsplit_once('@') {
+            Some((local, domain)) => {
+                let mut redacted = redactor.redact(local.to_string());
+                redacted.push('@');
+                redacted.push_str(domain);
+                redacted
+            }
+            None => redactor.redact(email.to_string()),
+        }
+    }
+
+    /// Checks the [`when`](RedactorBuilder::when) predicate against the whole of the data, returning a `Redactor` that
+    /// redacts its parts without checking it again, or `None` if the data shouldn't be redacted at all.
+    fn unconditional(&self, data: &str) -> Option<Redactor> {
+        if self.when.is_some_and(|when| !when(data)) {
+            return None;
+        }
+        Some(Redactor {
+            flags: self.flags,
+            when: None,
+        })
+    }
+
     /// Redact the given string in-place.
     //
     /// Can be chained for convenience.
@@ -119,7 +679,51 @@ impl Redactor {
     /// );
     /// ```
     pub fn redact_in_place(&self, data: &mut String) -> &Self {
-        *data = self.redact(core::mem::take(data));
+        *data = self.redact_owned(core::mem::take(data));
+        self
+    }
+
+    /// Redact every value of the given map, returning a new map with the same keys.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use veil::redactor::Redactor;
+    /// # use std::collections::HashMap;
+    /// let config = HashMap::from([("database_url", "postgres://localhost".to_string())]);
+    ///
+    /// let redactor = Redactor::builder().partial().build().unwrap();
+    ///
+    /// assert_eq!(
+    ///     redactor.redact_map(&config),
+    ///     HashMap::from([("database_url", "pos*****://******ost".to_string())])
+    /// );
+    /// ```
+    pub fn redact_map<K: Clone + Eq + Hash>(&self, map: &HashMap<K, String>) -> HashMap<K, String> {
+        map.iter()
+            .map(|(key, value)| (key.clone(), self.redact(value.clone())))
+            .collect()
+    }
+
+    /// Redact every value of the given map in-place, leaving the keys as-is.
+    //
+    /// Can be chained for convenience.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use veil::redactor::Redactor;
+    /// # use std::collections::HashMap;
+    /// let mut config = HashMap::from([("api_key", "sk_live_1234".to_string())]);
+    ///
+    /// Redactor::builder().build().unwrap().redact_map_in_place(&mut config);
+    ///
+    /// assert_eq!(config["api_key"], "**_****_****");
+    /// ```
+    pub fn redact_map_in_place<K>(&self, map: &mut HashMap<K, String>) -> &Self {
+        for value in map.values_mut() {
+            self.redact_in_place(value);
+        }
         self
     }
 
@@ -127,6 +731,32 @@ impl Redactor {
     ///
     /// Currently, the only supported [`Debug`] formats are `{:?}` and `{:#?}`. Other flags will be ignored.
     ///
+    /// # Redacting whole types
+    ///
+    /// This works for any [`Debug`] type, including ones that don't derive [`Redact`](crate::Redact), but the
+    /// *entire* output is redacted as a single string:
+    ///
+    /// * Type, variant and field names are redacted too, only punctuation such as `{`, `:` and `"` is left as-is.
+    /// * With [`partial`](RedactorBuilder::partial), only the beginning and the end of the whole output are exposed,
+    ///   not of each field. The end is usually the last field's value, so that may be partially exposed.
+    ///
+    /// ```rust
+    /// # use veil::redactor::Redactor;
+    /// #[derive(Debug)]
+    /// struct Customer {
+    ///     name: &'static str,
+    ///     tags: Vec<&'static str>,
+    /// }
+    ///
+    /// let customer = Customer { name: "John Doe", tags: vec!["vip"] };
+    ///
+    /// let redactor = Redactor::builder().partial().build().unwrap();
+    /// assert_eq!(
+    ///     format!("{:?}", redactor.wrap(&customer)),
+    ///     "Cus***** { ****: \"**** ***\", ****: [\"vip\"] }"
+    /// );
+    /// ```
+    ///
     /// # Example
     ///
     /// ```rust
@@ -154,7 +784,45 @@ impl Redactor {
     /// );
     /// ```
     pub const fn wrap<'a, T>(&'a self, data: &'a T) -> RedactWrapped<'a, T> {
-        RedactWrapped { flags: &self.0, data }
+        RedactWrapped { redactor: self, data }
+    }
+}
+
+/// Shows how the `Redactor` is configured.
+///
+/// The [`when`](RedactorBuilder::when) predicate can't be shown, only whether there is one.
+///
+/// # Example
+///
+/// ```rust
+/// # use veil::redactor::Redactor;
+/// let redactor = Redactor::builder().char('X').partial().build().unwrap();
+///
+/// assert_eq!(
+///     format!("{redactor:?}"),
+///     "Redactor { length: Partial, char: 'X', digits_only: false, cycle: None, cjk: false, preserve_class: None, \
+///      pad: None, min_mask_len: None, min_partial_chars: None, expose_side: None, secret: false, hash: None, \
+///      when: false }"
+/// );
+/// ```
+impl Debug for Redactor {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let flags = &self.flags;
+        fmt.debug_struct("Redactor")
+            .field("length", &flags.redact_length)
+            .field("char", &flags.redact_char)
+            .field("digits_only", &flags.digits_only)
+            .field("cycle", &flags.redact_cycle)
+            .field("cjk", &flags.cjk)
+            .field("preserve_class", &flags.preserve_class)
+            .field("pad", &flags.pad)
+            .field("min_mask_len", &flags.min_mask_len)
+            .field("min_partial_chars", &flags.min_partial_chars)
+            .field("expose_side", &flags.expose_side)
+            .field("secret", &flags.secret)
+            .field("hash", &flags.hash)
+            .field("when", &self.when.is_some())
+            .finish()
     }
 }
 
@@ -162,6 +830,23 @@ impl Redactor {
 pub struct RedactorBuilder {
     redact_char: Option<char>,
     partial: bool,
+    expose_pct: Option<u8>,
+    min_mask_len: Option<u8>,
+    when: Option<fn(&str) -> bool>,
+    expose: Option<u8>,
+    expose_start: bool,
+    expose_end: bool,
+    mask_prefix: Option<u8>,
+    mask_suffix: Option<u8>,
+    fixed: Option<u8>,
+
+    // These have no setters yet, they're only set when parsing a `Redactor` from modifiers, see `FromStr`.
+    min_partial_chars: Option<NonZeroU8>,
+    cjk: bool,
+    digits_only: bool,
+    pad: Option<NonZeroU8>,
+    align: Option<std::fmt::Alignment>,
+    preserve_class: Option<PreserveClass>,
 }
 impl RedactorBuilder {
     /// Initialize a new redaction flag builder.
@@ -170,6 +855,21 @@ impl RedactorBuilder {
         Self {
             redact_char: None,
             partial: false,
+            expose_pct: None,
+            min_mask_len: None,
+            when: None,
+            expose: None,
+            expose_start: false,
+            expose_end: false,
+            mask_prefix: None,
+            mask_suffix: None,
+            fixed: None,
+            min_partial_chars: None,
+            cjk: false,
+            digits_only: false,
+            pad: None,
+            align: None,
+            preserve_class: None,
         }
     }
 
@@ -191,27 +891,308 @@ impl RedactorBuilder {
         self
     }
 
+    /// Expose the given percentage of the data at each end of a partial redaction, instead of at most 3 characters.
+    ///
+    /// Equivalent to `#[redact(partial, expose_pct = ...)]` when deriving. Requires [`partial`](Self::partial) and
+    /// must be between 0 and 49.
+    #[inline(always)]
+    pub const fn expose_pct(mut self, pct: u8) -> Self {
+        self.expose_pct = Some(pct);
+        self
+    }
+
+    /// Expose at most the given number of characters at each end of a partial redaction, instead of at most 3.
+    ///
+    /// Equivalent to `#[redact(partial, expose = ...)]` when deriving. Requires [`partial`](Self::partial), must be
+    /// greater than zero and can't be combined with [`expose_pct`](Self::expose_pct).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use veil::redactor::Redactor;
+    /// let redactor = Redactor::builder().partial().expose(1).build().unwrap();
+    ///
+    /// assert_eq!(redactor.redact("039845734895".to_string()), "0**********5");
+    /// ```
+    #[inline(always)]
+    pub const fn expose(mut self, max: u8) -> Self {
+        self.expose = Some(max);
+        self
+    }
+
+    /// Only expose the beginning of the data in a partial redaction, redacting its end too.
+    ///
+    /// Equivalent to `#[redact(partial, expose_start)]` when deriving. Requires [`partial`](Self::partial) and can't
+    /// be combined with [`expose_end`](Self::expose_end).
+    #[inline(always)]
+    pub const fn expose_start(mut self) -> Self {
+        self.expose_start = true;
+        self
+    }
+
+    /// Only expose the end of the data in a partial redaction, redacting its beginning too.
+    ///
+    /// Equivalent to `#[redact(partial, expose_end)]` when deriving. Requires [`partial`](Self::partial) and can't
+    /// be combined with [`expose_start`](Self::expose_start).
+    #[inline(always)]
+    pub const fn expose_end(mut self) -> Self {
+        self.expose_end = true;
+        self
+    }
+
+    /// Redact exactly this many characters at the beginning of the data, exposing the rest.
+    ///
+    /// Equivalent to `#[redact(mask_prefix = ...)]` when deriving. Can be combined with
+    /// [`mask_suffix`](Self::mask_suffix), but not with [`partial`](Self::partial).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use veil::redactor::Redactor;
+    /// let redactor = Redactor::builder().mask_prefix(4).mask_suffix(2).build().unwrap();
+    ///
+    /// assert_eq!(redactor.redact("AB12-3456-78CD".to_string()), "****-3456-78**");
+    /// ```
+    #[inline(always)]
+    pub const fn mask_prefix(mut self, len: u8) -> Self {
+        self.mask_prefix = Some(len);
+        self
+    }
+
+    /// Redact exactly this many characters at the end of the data, exposing the rest.
+    ///
+    /// Equivalent to `#[redact(mask_suffix = ...)]` when deriving. Can be combined with
+    /// [`mask_prefix`](Self::mask_prefix), but not with [`partial`](Self::partial).
+    #[inline(always)]
+    pub const fn mask_suffix(mut self, len: u8) -> Self {
+        self.mask_suffix = Some(len);
+        self
+    }
+
+    /// Redact the data with exactly this many characters, no matter how long it is, so that its length isn't shown.
+    ///
+    /// Equivalent to `#[redact(fixed = ...)]` when deriving. Must be greater than zero and can't be combined with
+    /// [`partial`](Self::partial), [`mask_prefix`](Self::mask_prefix), [`mask_suffix`](Self::mask_suffix) or
+    /// [`min_mask_len`](Self::min_mask_len).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use veil::redactor::Redactor;
+    /// let redactor = Redactor::builder().fixed(4).build().unwrap();
+    ///
+    /// assert_eq!(redactor.redact("hunter2".to_string()), "****");
+    /// ```
+    #[inline(always)]
+    pub const fn fixed(mut self, width: u8) -> Self {
+        self.fixed = Some(width);
+        self
+    }
+
+    /// Redact with at least this many characters, adding more after the last redacted character of shorter data, so that
+    /// a short secret can't be told apart from a longer one.
+    ///
+    /// Data without any characters to redact, such as an empty string, is left as-is. This has no equivalent when
+    /// deriving, and can't be combined with [`partial`](Self::partial), [`mask_prefix`](Self::mask_prefix) or
+    /// [`mask_suffix`](Self::mask_suffix), which don't redact all of the data, or with [`fixed`](Self::fixed), which
+    /// already hides its length.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use veil::redactor::Redactor;
+    /// let redactor = Redactor::builder().min_mask_len(8).build().unwrap();
+    ///
+    /// assert_eq!(redactor.redact("abc".to_string()), "********");
+    /// assert_eq!(redactor.redact("hunter2-hunter2".to_string()), "*******-*******");
+    /// ```
+    #[inline(always)]
+    pub const fn min_mask_len(mut self, len: u8) -> Self {
+        self.min_mask_len = Some(len);
+        self
+    }
+
+    /// Only redact data that matches the given predicate, passing everything else through as-is.
+    ///
+    /// The predicate is called with *all* of the data being redacted at once, e.g. the whole string passed to
+    /// [`Redactor::redact`], the whole address passed to [`Redactor::redact_email`] or each line written to a
+    /// [`RedactorWriter`](crate::stream::RedactorWriter), not with each word in it. With [`Redactor::wrap`], it's
+    /// called with the formatted output of the wrapped data.
+    ///
+    /// This has no equivalent when deriving, and is ignored when the `Redactor` is used with `#[redact(policy = ...)]`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use veil::redactor::Redactor;
+    /// let redactor = Redactor::builder()
+    ///     .when(|data| data.len() == 11 && data.chars().filter(|char| *char == '-').count() == 2)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(redactor.redact("123-45-6789".to_string()), "***-**-****");
+    /// assert_eq!(redactor.redact("John Doe".to_string()), "John Doe");
+    /// ```
+    #[inline(always)]
+    pub const fn when(mut self, predicate: fn(&str) -> bool) -> Self {
+        self.when = Some(predicate);
+        self
+    }
+
     /// Build the redaction flags.
     ///
     /// Returns an error if the state of the builder is invalid.
     /// The error will be optimised away by the compiler if the builder is valid at compile time, so it's safe and zero-cost to use `unwrap` on the result if you are constructing this at compile time.
     #[inline(always)]
     pub const fn build(self) -> Result<Redactor, &'static str> {
-        let mut flags = RedactFlags {
-            redact_length: if self.partial {
-                RedactionLength::Partial
-            } else {
-                RedactionLength::Full
+        let partial = self.partial;
+        let expose = match self.expose {
+            Some(max) => match NonZeroU8::new(max) {
+                Some(max) => Some(max),
+                None => return Err("`expose` must be greater than zero, use `#[redact]` to expose nothing"),
+            },
+            None => None,
+        };
+        let expose_side = match (self.expose_start, self.expose_end) {
+            (true, true) => {
+                return Err("`expose_start` and `expose_end` can't be combined, use just `partial` to expose both ends")
+            }
+            (true, false) => Some(ExposeSide::Start),
+            (false, true) => Some(ExposeSide::End),
+            (false, false) => None,
+        };
+        let fixed = match self.fixed {
+            Some(width) => match NonZeroU8::new(width) {
+                Some(width) => Some(width),
+                None => return Err("fixed redacting width must be greater than zero"),
+            },
+            None => None,
+        };
+        let redact_length = match (partial, fixed, self.mask_prefix.is_some() || self.mask_suffix.is_some()) {
+            (true, Some(_), _) => return Err("`partial` and `fixed` can't be combined"),
+            (true, None, true) | (false, Some(_), true) => {
+                return Err("`mask_prefix` and `mask_suffix` can't be combined with `partial` or `fixed`")
+            }
+            (true, None, false) => match (self.expose_pct, expose) {
+                (Some(_), Some(_)) => return Err("`expose` can't be combined with `expose_pct`"),
+                (Some(pct), None) if pct > 49 => {
+                    return Err("`expose_pct` must be between 0 and 49, as half or more of the data would be exposed")
+                }
+                (Some(pct), None) => RedactionLength::PartialPercent(pct),
+                (None, Some(max))
+                    if match self.min_partial_chars {
+                        Some(min) => min.get() < max.get().saturating_mul(2),
+                        None => false,
+                    } =>
+                {
+                    return Err("`min` must be at least twice `expose`, so that data is never exposed entirely")
+                }
+                (None, Some(max)) => RedactionLength::PartialExpose(max.get()),
+                (None, None) => RedactionLength::Partial,
             },
+            (false, Some(width), false) => RedactionLength::Fixed(width),
+            (false, None, true) => RedactionLength::MaskEnds {
+                prefix: match self.mask_prefix {
+                    Some(prefix) => prefix,
+                    None => 0,
+                },
+                suffix: match self.mask_suffix {
+                    Some(suffix) => suffix,
+                    None => 0,
+                },
+            },
+            (false, None, false) => RedactionLength::Full,
+        };
 
-            redact_char: '*',
+        let min_mask_len = match self.min_mask_len {
+            Some(_) if partial => return Err("`min_mask_len` can't be combined with `partial`"),
+            // Both redact with exactly as many characters as they're told to, whatever the floor.
+            Some(_) if fixed.is_some() => return Err("`min_mask_len` can't be combined with `fixed`"),
+            Some(_) if self.mask_prefix.is_some() || self.mask_suffix.is_some() => {
+                return Err("`min_mask_len` can't be combined with `mask_prefix` or `mask_suffix`")
+            }
+            Some(len) => match NonZeroU8::new(len) {
+                Some(len) => Some(len),
+                None => return Err("`min_mask_len` must be greater than zero"),
+            },
+            None => None,
         };
 
-        if let Some(char) = self.redact_char {
-            flags.redact_char = char;
+        let pad = match (self.pad, self.align) {
+            (Some(width), align) => Some(Padding {
+                width: width.get(),
+                align: match align {
+                    Some(align) => align,
+                    None => std::fmt::Alignment::Left,
+                },
+            }),
+            (None, Some(_)) => return Err("`align` requires `pad`"),
+            (None, None) => None,
+        };
+
+        if !partial && self.expose_pct.is_some() {
+            return Err("`expose_pct` requires `partial`");
+        } else if !partial && expose.is_some() {
+            return Err("`expose` requires `partial`");
+        } else if !partial && self.min_partial_chars.is_some() {
+            return Err("`min` requires `partial`");
+        } else if !partial && self.cjk {
+            return Err("`cjk` requires `partial`");
+        } else if !partial && expose_side.is_some() {
+            return Err("`expose_start` and `expose_end` require `partial`");
+        } else if self.cjk && expose_side.is_some() {
+            return Err(
+                "`cjk` can't be combined with `expose_start` or `expose_end`, it always exposes the first character",
+            );
+        } else if self.preserve_class.is_some() && fixed.is_some() {
+            return Err("`preserve_class` can't be combined with `fixed`, as none of the data is shown");
+        } else if self.preserve_class.is_some() && self.redact_char.is_some() {
+            return Err("`preserve_class` can't be combined with `with`, it decides which characters to redact with");
         }
 
-        Ok(Redactor(flags))
+        let flags = RedactFlags {
+            redact_length,
+            redact_char: match self.redact_char {
+                Some(char) => char,
+                None => RedactFlags::DEFAULT_REDACT_CHAR,
+            },
+            redact_cycle: None,
+            digits_only: self.digits_only,
+            cjk: self.cjk,
+            preserve_class: self.preserve_class,
+            pad,
+            min_mask_len,
+            min_partial_chars: self.min_partial_chars,
+            expose_side,
+            secret: false,
+            hash: None,
+        };
+
+        Ok(Redactor { flags, when: self.when })
+    }
+
+    /// Build the redaction flags into a `Redactor` that lives for the rest of the program, for storing in global state
+    /// or passing to long-lived loggers when it's only configured at runtime, e.g. at startup.
+    ///
+    /// The `Redactor` is leaked on purpose to get the `'static` reference, so this should only be called once, not
+    /// every time something is redacted. Use [`RedactorBuilder::build`] for `Redactor`s that are known at compile
+    /// time, which can be stored in a `const` or `static` instead.
+    ///
+    /// Returns an error if the state of the builder is invalid, without leaking anything.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use veil::redactor::RedactorBuilder;
+    /// let char = std::env::var("REDACT_CHAR").map_or('*', |char| char.chars().next().unwrap_or('*'));
+    /// let redactor = RedactorBuilder::new().char(char).build_static().unwrap();
+    ///
+    /// // The reference can be moved into threads without wrapping the `Redactor` in an `Arc`.
+    /// let logger = std::thread::spawn(move || redactor.redact("John Doe".to_string()));
+    /// assert_eq!(logger.join().unwrap(), "**** ***");
+    /// ```
+    pub fn build_static(self) -> Result<&'static Redactor, &'static str> {
+        self.build().map(|redactor| &*Box::leak(Box::new(redactor)))
     }
 }
 
@@ -220,3 +1201,121 @@ impl Default for RedactorBuilder {
         Self::new()
     }
 }
+
+/// Parses a `Redactor` from the same modifiers as `#[redact(...)]`, e.g. `"partial, with = 'X'"`, so that it redacts data
+/// exactly like a field with that attribute would. Useful for configuring redaction at runtime, such as from a config file.
+///
+/// Only modifiers that change how data is redacted are accepted: `partial`, `expose_pct`, `expose`, `min`,
+/// `expose_start`, `expose_end`, `cjk`, `with` (a `char`), `fixed`, `digits_only`, `preserve_class`, `mask_prefix`,
+/// `mask_suffix`, `pad` and `align`.
+///
+/// # Example
+///
+/// ```rust
+/// # use veil::redactor::Redactor;
+/// let redactor: Redactor = "partial, with = 'X'".parse().unwrap();
+/// assert_eq!(redactor.redact("john.doe@prima.it".to_string()), "johX.XXX@XXXXa.it");
+/// ```
+impl FromStr for Redactor {
+    type Err = &'static str;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let mut builder = RedactorBuilder::new();
+        for (name, value) in parse_modifiers(spec) {
+            match (name, value) {
+                ("", None) => {}
+                ("partial", None) => builder.partial = true,
+                ("cjk", None) => builder.cjk = true,
+                ("expose_start", None) => builder.expose_start = true,
+                ("expose_end", None) => builder.expose_end = true,
+                ("digits_only", None) => builder.digits_only = true,
+                // A string `with` is only valid with `cycle`, which needs the string to live forever.
+                ("with", Some(value)) if value.starts_with('"') => {
+                    return Err("`cycle` is not supported when parsing a `Redactor`")
+                }
+                ("with", Some(value)) => builder.redact_char = Some(parse_char(value)?),
+                ("expose_pct", Some(value)) => builder.expose_pct = Some(parse_u8(value)?),
+                ("min", Some(value)) => {
+                    builder.min_partial_chars =
+                        Some(NonZeroU8::new(parse_u8(value)?).ok_or("`min` must be greater than zero")?);
+                }
+                ("expose", Some(value)) => builder.expose = Some(parse_u8(value)?),
+                ("fixed", Some(value)) => builder.fixed = Some(parse_u8(value)?),
+                ("mask_prefix", Some(value)) => builder.mask_prefix = Some(parse_u8(value)?),
+                ("mask_suffix", Some(value)) => builder.mask_suffix = Some(parse_u8(value)?),
+                ("pad", Some(value)) => {
+                    builder.pad =
+                        Some(NonZeroU8::new(parse_u8(value)?).ok_or("`pad` width must be greater than zero")?);
+                }
+                ("align", Some(value)) => {
+                    builder.align = Some(match parse_quoted(value, '"')? {
+                        "left" => std::fmt::Alignment::Left,
+                        "right" => std::fmt::Alignment::Right,
+                        "center" => std::fmt::Alignment::Center,
+                        _ => return Err("unknown alignment, `align` only supports `left`, `right` and `center`"),
+                    });
+                }
+                ("preserve_class", None) => {
+                    builder.preserve_class = Some(PreserveClass {
+                        letter: 'x',
+                        digit: '0',
+                    });
+                }
+                ("preserve_class", Some(value)) => {
+                    let mut chars = parse_quoted(value, '"')?.chars();
+                    let (Some(letter), Some(digit), None) = (chars.next(), chars.next(), chars.next()) else {
+                        return Err("`preserve_class` expects two characters, the one to redact letters with and the one to redact digits with");
+                    };
+                    builder.preserve_class = Some(PreserveClass { letter, digit });
+                }
+                ("cycle", None) => return Err("`cycle` is not supported when parsing a `Redactor`"),
+                _ => return Err("unknown or malformed redaction modifier"),
+            }
+        }
+
+        builder.build()
+    }
+}
+
+/// Splits a list of modifiers on the commas that aren't quoted, e.g. in `with = ','`.
+fn split_modifiers(spec: &str) -> impl Iterator<Item = &str> {
+    let mut quote = None;
+    spec.split(move |char: char| {
+        match quote {
+            Some(open) if char == open => quote = None,
+            None if char == '\'' || char == '"' => quote = Some(char),
+            _ => {}
+        }
+        quote.is_none() && char == ','
+    })
+}
+
+/// Splits a list of modifiers into their names and values, e.g. `fixed = 3` into `("fixed", Some("3"))`.
+fn parse_modifiers(spec: &str) -> impl Iterator<Item = (&str, Option<&str>)> {
+    split_modifiers(spec).map(|modifier| match modifier.split_once('=') {
+        Some((name, value)) => (name.trim(), Some(value.trim())),
+        None => (modifier.trim(), None),
+    })
+}
+
+/// Strips the given quotes from a modifier's value.
+fn parse_quoted(value: &str, quote: char) -> Result<&str, &'static str> {
+    value
+        .strip_prefix(quote)
+        .and_then(|value| value.strip_suffix(quote))
+        .ok_or("expected a quoted value")
+}
+
+/// Parses a modifier's value as a quoted `char`, e.g. `'X'`.
+fn parse_char(value: &str) -> Result<char, &'static str> {
+    let mut chars = parse_quoted(value, '\'')?.chars();
+    match (chars.next(), chars.next()) {
+        (Some(char), None) => Ok(char),
+        _ => Err("expected a single quoted `char`, such as `'X'`"),
+    }
+}
+
+/// Parses a modifier's value as an integer between 0 and 255.
+fn parse_u8(value: &str) -> Result<u8, &'static str> {
+    value.parse().map_err(|_| "expected an integer between 0 and 255")
+}
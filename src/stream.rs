@@ -0,0 +1,225 @@
+//! The [`RedactorWriter`] redacts data streamed through an [`io::Write`], such as a large log file,
+//! without loading all of it into memory.
+
+use crate::{private::RedactionLength, redactor::Redactor};
+use std::io::{self, Write};
+
+/// Wraps an [`io::Write`] and redacts everything written to it with a [`Redactor`].
+///
+/// The output is the same as if [`Redactor::redact`] was called on each line separately, however the data is split
+/// across writes. Multibyte UTF-8 characters can be split across writes too, and invalid UTF-8 is replaced with
+/// `U+FFFD`.
+///
+/// # Buffering
+///
+/// With full redaction, each run of redacted characters is redacted on its own, so only the run at the end of the data
+/// written so far is buffered, along with the start of a multibyte UTF-8 character that hasn't been completely written
+/// yet. Everything before it is redacted and written straight away.
+///
+/// Anything else, such as [`partial`](crate::redactor::RedactorBuilder::partial) redaction, depends on the whole line,
+/// so the current line is buffered until its newline is written. Make sure such data is split into lines, as a single
+/// line is never redacted before it's complete.
+///
+/// A trailing line without a newline, or a trailing run of redacted characters, is only written by
+/// [`finish`](RedactorWriter::finish), or when the writer is dropped, in which case any error is ignored.
+///
+/// At worst, the writer holds on to the longest line (or run) written to it, so the memory it uses grows with its
+/// length. Each byte written is only looked at once to find where the data can be split, so writing a long line in many
+/// small chunks still takes time proportional to its length.
+///
+/// # Errors
+///
+/// Data is consumed by [`write`](Write::write) before it's redacted, so if writing the redacted data to the wrapped
+/// writer fails, the error is returned by the next call to `write`, [`flush`](Write::flush) or
+/// [`finish`](RedactorWriter::finish) instead, like [`BufWriter`](io::BufWriter) does. The redacted data is kept until
+/// it has been written.
+///
+/// # Example
+///
+/// ```rust
+/// # use veil::{redactor::Redactor, stream::RedactorWriter};
+/// # use std::io::Write;
+/// let redactor = Redactor::builder().partial().build().unwrap();
+///
+/// let mut writer = RedactorWriter::new(redactor, Vec::new());
+/// writer.write_all(b"john.doe@prima.it\nJohn ").unwrap();
+/// writer.write_all(b"Doe").unwrap();
+/// writer.finish().unwrap();
+///
+/// assert_eq!(writer.get_ref(), b"joh*.***@****a.it\nJo** *oe");
+/// ```
+pub struct RedactorWriter<W: Write> {
+    redactor: Redactor,
+    inner: W,
+
+    /// The data that hasn't been redacted yet, see [Buffering](RedactorWriter#buffering).
+    pending: Vec<u8>,
+
+    /// How many bytes at the start of `pending` are already known not to be followed by a point the data can be split
+    /// at, so that they aren't scanned again.
+    scanned: usize,
+
+    /// The redacted data that hasn't been written to `inner` yet, because writing it failed.
+    redacted: Vec<u8>,
+}
+impl<W: Write> RedactorWriter<W> {
+    /// Wraps the given writer, redacting everything written to it with the given [`Redactor`].
+    pub fn new(redactor: Redactor, inner: W) -> Self {
+        Self {
+            redactor,
+            inner,
+            pending: Vec::new(),
+            scanned: 0,
+            redacted: Vec::new(),
+        }
+    }
+
+    /// Returns a reference to the wrapped writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the wrapped writer.
+    ///
+    /// Writing to it directly bypasses redaction.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Redacts and writes everything that's still buffered, even if the current line doesn't end with a newline yet,
+    /// then flushes the wrapped writer.
+    pub fn finish(&mut self) -> io::Result<()> {
+        self.redact_pending(true);
+        self.write_redacted()?;
+        self.inner.flush()
+    }
+
+    /// Whether each run of redacted characters is redacted the same no matter what's around it, so that the data
+    /// doesn't need to be buffered until the end of the line.
+    fn is_run_local(&self) -> bool {
+        let flags = &self.redactor.flags;
+        flags.redact_length == RedactionLength::Full
+            && flags.redact_cycle.is_none()
+            && flags.min_mask_len.is_none()
+            && flags.pad.is_none()
+            && flags.hash.is_none()
+            && self.redactor.when.is_none()
+    }
+
+    /// Redacts as much of the pending data as can be redacted without knowing what's written next, or all of it once
+    /// `finished`.
+    fn redact_pending(&mut self, finished: bool) {
+        let complete = if finished {
+            self.pending.len()
+        } else {
+            self.pending.len() - incomplete_char_len(&self.pending)
+        };
+
+        // Only the data written since the last call can contain a new point to split at.
+        let end = if finished {
+            complete
+        } else if self.is_run_local() {
+            self.last_unmasked_end(&self.pending[self.scanned..complete])
+                .map_or(0, |end| self.scanned + end)
+        } else {
+            // A newline byte can never be part of a multibyte UTF-8 character, so complete lines are always valid.
+            self.pending[self.scanned..complete]
+                .iter()
+                .rposition(|&byte| byte == b'\n')
+                .map_or(0, |i| self.scanned + i + 1)
+        };
+
+        if end > 0 {
+            let data = String::from_utf8_lossy(&self.pending[..end]);
+            for line in data.split_inclusive('\n') {
+                let redacted = self.redactor.redact_display(&line);
+                self.redacted.extend_from_slice(redacted.as_bytes());
+            }
+            self.pending.drain(..end);
+        }
+        self.scanned = complete - end;
+    }
+
+    /// Returns the offset right after the last character in `data` that isn't masked, if there is one.
+    ///
+    /// Invalid UTF-8 is replaced with `U+FFFD`, which is never masked.
+    fn last_unmasked_end(&self, data: &[u8]) -> Option<usize> {
+        let mut offset = 0;
+        let mut end = None;
+        for chunk in data.utf8_chunks() {
+            let valid = chunk.valid();
+            if let Some((i, char)) = valid
+                .char_indices()
+                .rev()
+                .find(|(_, char)| !self.redactor.flags.is_masked(*char))
+            {
+                end = Some(offset + i + char.len_utf8());
+            }
+            offset += valid.len() + chunk.invalid().len();
+            if !chunk.invalid().is_empty() {
+                end = Some(offset);
+            }
+        }
+        end
+    }
+
+    /// Writes the redacted data to the wrapped writer, keeping whatever couldn't be written.
+    fn write_redacted(&mut self) -> io::Result<()> {
+        while !self.redacted.is_empty() {
+            match self.inner.write(&self.redacted) {
+                Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                Ok(written) => drop(self.redacted.drain(..written)),
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+}
+impl<W: Write> Write for RedactorWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // Nothing of `buf` has been consumed yet, so a previous error can still be returned.
+        self.write_redacted()?;
+
+        self.pending.extend_from_slice(buf);
+        self.redact_pending(false);
+
+        // `buf` has been consumed, so an error here is returned by the next call instead.
+        let _ = self.write_redacted();
+        Ok(buf.len())
+    }
+
+    /// Writes any redacted data that couldn't be written yet and flushes the wrapped writer.
+    ///
+    /// Data that is still buffered isn't redacted yet, see [`finish`](RedactorWriter::finish).
+    fn flush(&mut self) -> io::Result<()> {
+        self.write_redacted()?;
+        self.inner.flush()
+    }
+}
+impl<W: Write> Drop for RedactorWriter<W> {
+    fn drop(&mut self) {
+        let _ = self.finish();
+    }
+}
+
+/// Returns how many bytes at the end of the data are the start of a multibyte UTF-8 character that hasn't been
+/// completely written yet.
+fn incomplete_char_len(data: &[u8]) -> usize {
+    for len in 1..=data.len().min(3) {
+        let byte = data[data.len() - len];
+        // Continuation bytes are skipped until the byte that starts the character.
+        if byte & 0b1100_0000 == 0b1000_0000 {
+            continue;
+        }
+
+        let char_len = match byte {
+            0b1100_0000..=0b1101_1111 => 2,
+            0b1110_0000..=0b1110_1111 => 3,
+            0b1111_0000..=0b1111_0111 => 4,
+            _ => 1,
+        };
+        return if char_len > len { len } else { 0 };
+    }
+    0
+}
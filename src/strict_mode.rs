@@ -0,0 +1,25 @@
+#![cfg_attr(docsrs, doc(cfg(feature = "strict-mode")))]
+
+//! Makes veil panic if redacting a field ever produces its original value
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static STRICT_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Turns on strict mode for the rest of the process, making veil panic if redacting data produces the original data,
+/// e.g. because of a misconfigured `#[redact(...)]` attribute. It can't be turned off again.
+///
+/// This is a safety net for tests and CI, and shouldn't be turned on in production: every redacted value is redacted
+/// into a temporary [`String`] and compared against the original, roughly doubling the cost of redaction.
+///
+/// Data without any characters that would be redacted, such as `None` or a `#[redact(digits_only)]` field without any
+/// digits, is never considered a failure. The panic message never includes the data itself.
+#[cfg_attr(docsrs, doc(cfg(feature = "strict-mode")))]
+pub fn strict_mode() {
+    STRICT_MODE.store(true, Ordering::Relaxed);
+}
+
+/// Whether [`strict_mode`] has been turned on.
+pub(crate) fn is_enabled() -> bool {
+    STRICT_MODE.load(Ordering::Relaxed)
+}
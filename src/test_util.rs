@@ -0,0 +1,72 @@
+#![cfg_attr(docsrs, doc(cfg(feature = "test-util")))]
+
+//! Helpers for testing that your own types are redacted
+
+/// Asserts that neither the `{:?}` nor the `{:#?}` output of a value contains any of the given sensitive data.
+///
+/// The value is borrowed, and each piece of sensitive data can be anything that implements [`AsRef<str>`]. The panic
+/// message shows the redacted output and the data it contains.
+///
+/// # Example
+///
+/// ```rust
+/// # use veil::Redact;
+/// #[derive(Redact)]
+/// struct Customer {
+///     #[redact(partial)]
+///     name: String,
+///     #[redact]
+///     email: String,
+/// }
+///
+/// let customer = Customer {
+///     name: "William".to_string(),
+///     email: "john.doe@prima.it".to_string(),
+/// };
+///
+/// veil::assert_redacts!(customer, contains_none_of: ["William", "john.doe@prima.it"]);
+/// ```
+///
+/// Sensitive data that isn't redacted fails the assertion:
+///
+/// ```rust,should_panic
+/// # use veil::Redact;
+/// #[derive(Redact)]
+/// struct Customer {
+///     #[redact(partial)]
+///     name: String,
+///     email: String,
+/// }
+///
+/// let customer = Customer {
+///     name: "William".to_string(),
+///     email: "john.doe@prima.it".to_string(),
+/// };
+///
+/// veil::assert_redacts!(customer, contains_none_of: ["William", "john.doe@prima.it"]);
+/// ```
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "test-util")))]
+macro_rules! assert_redacts {
+    ($value:expr, contains_none_of: [$($sensitive:expr),* $(,)?] $(,)?) => {
+        match &$value {
+            value => {
+                for (format, redacted) in [
+                    ("{:?}", ::std::format!("{:?}", value)),
+                    ("{:#?}", ::std::format!("{:#?}", value)),
+                ] {
+                    $({
+                        let sensitive: &::std::primitive::str = ::std::convert::AsRef::as_ref(&$sensitive);
+                        ::std::assert!(
+                            !redacted.contains(sensitive),
+                            "the `{}` output {:?} contains sensitive data: {:?}",
+                            format,
+                            redacted,
+                            sensitive,
+                        );
+                    })*
+                }
+            }
+        }
+    };
+}
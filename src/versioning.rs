@@ -42,6 +42,29 @@ fn test_macros_version() {
     );
 }
 
+#[test]
+fn test_runtime_version() {
+    // Ensure that `veil::version()` is a valid semver version, e.g. `1.2.3` or `1.2.3-rc.1+build`
+
+    let version = crate::version();
+    assert!(!version.is_empty());
+    assert_eq!(version, env!("CARGO_PKG_VERSION"));
+
+    let core = version.split(['-', '+']).next().unwrap();
+    let parts = core.split('.').collect::<Vec<_>>();
+    assert_eq!(
+        parts.len(),
+        3,
+        "{version:?} should have a major, minor and patch version"
+    );
+    for part in parts {
+        assert!(
+            part.parse::<u64>().is_ok() && (part == "0" || !part.starts_with('0')),
+            "{version:?} is not a valid semver version"
+        );
+    }
+}
+
 #[test]
 fn test_macros_crate_version() {
     // Ensure that the version in `veil-macros` in the `[dependencies]` table in `Cargo.toml` matches the version of `veil`
@@ -1,6 +1,6 @@
 use crate::{
     flags::{ExtractFlags, FieldFlags, FieldFlagsParse},
-    fmt::{self, FormatData},
+    fmt::{self, FieldAccess, FormatData},
     redact::UnusedDiagnostic,
 };
 use proc_macro::TokenStream;
@@ -20,23 +20,59 @@ pub(super) fn derive_redact(
     name_ident: syn::Ident,
     unused: &mut UnusedDiagnostic,
 ) -> Result<TokenStream, syn::Error> {
-    // Parse #[redact(all, variant, ...)] from the enum attributes, if present.
-    let top_level_flags = match FieldFlags::extract::<1>("Redact", &attrs, FieldFlagsParse { skip_allowed: false })? {
-        [Some(flags)] => {
-            if !flags.all || !flags.variant {
-                return Err(syn::Error::new(
-                    attrs[0].span(),
-                    "at least `#[redact(all, variant)]` is required here to redact all variant names",
-                ));
-            } else if flags.display {
-                return Err(syn::Error::new(attrs[0].span(), "`#[redact(display)]` is invalid here"));
-            } else {
-                Some(flags)
-            }
+    // Parse #[redact(all, variant, ...)] or #[redact(all, ...)] from the enum attributes, if present.
+    let mut top_level_flags = None;
+    let mut top_level_fields_flags = None;
+    let mut max_depth = None;
+    if let [Some(mut flags)] = FieldFlags::extract::<1>(
+        "Redact",
+        &attrs,
+        FieldFlagsParse {
+            skip_allowed: false,
+            transparent_allowed: false,
+            no_display_allowed: false,
+        },
+    )? {
+        // `#[redact(max_depth = N)]` applies to the enum itself, so it's allowed without `all`.
+        max_depth = flags.max_depth.take();
+        if max_depth.is_some() && flags == FieldFlags::default() {
+            // #[redact(max_depth = N)]
+        } else if !flags.all {
+            return Err(syn::Error::new(
+                attrs[0].span(),
+                "at least `#[redact(all, variant)]` is required here to redact all variant names, or `#[redact(all)]` to redact the fields of all variants",
+            ));
+        } else if flags.variant && flags.display {
+            return Err(syn::Error::new(attrs[0].span(), "`#[redact(display)]` is invalid here"));
+        } else if flags.rename.is_some() {
+            return Err(syn::Error::new(
+                attrs[0].span(),
+                "`rename` is only valid on named struct fields",
+            ));
+        } else if flags.order.is_some() {
+            return Err(syn::Error::new(attrs[0].span(), "`order` is only valid on fields"));
+        } else if !flags.extra_fields.is_empty() {
+            return Err(syn::Error::new(
+                attrs[0].span(),
+                "`extra_field` is only valid on structs with named fields",
+            ));
+        } else if flags.variant {
+            // #[redact(all, variant, ...)]
+            top_level_flags = Some(flags);
+        } else if e
+            .variants
+            .iter()
+            .all(|variant| matches!(variant.fields, syn::Fields::Unit))
+        {
+            return Err(syn::Error::new(
+                attrs[0].span(),
+                "`#[redact(all)]` has no effect because every variant is a unit variant, use `#[redact(all, variant)]` to redact all variant names",
+            ));
+        } else {
+            // #[redact(all, ...)]
+            top_level_fields_flags = Some(flags);
         }
-
-        _ => None,
-    };
+    }
 
     // Collect each variant's flags
     let mut variant_flags = Vec::with_capacity(e.variants.len());
@@ -45,7 +81,9 @@ pub(super) fn derive_redact(
             "Redact",
             &variant.attrs,
             FieldFlagsParse {
-                skip_allowed: top_level_flags.is_some(),
+                skip_allowed: top_level_flags.is_some() || top_level_fields_flags.is_some(),
+                transparent_allowed: false,
+                no_display_allowed: false,
             },
         )? {
             [None, None] => EnumVariantFieldFlags::default(),
@@ -126,16 +164,65 @@ pub(super) fn derive_redact(
             [None, ..] => unreachable!(),
         };
 
+        if [&flags.variant_flags, &flags.all_fields_flags]
+            .into_iter()
+            .flatten()
+            .any(|flags| flags.rename.is_some())
+        {
+            return Err(syn::Error::new(
+                variant.span(),
+                "`rename` is only valid on named struct fields",
+            ));
+        } else if [&flags.variant_flags, &flags.all_fields_flags]
+            .into_iter()
+            .flatten()
+            .any(|flags| flags.order.is_some())
+        {
+            return Err(syn::Error::new(variant.span(), "`order` is only valid on fields"));
+        } else if [&flags.variant_flags, &flags.all_fields_flags]
+            .into_iter()
+            .flatten()
+            .any(|flags| !flags.extra_fields.is_empty())
+        {
+            return Err(syn::Error::new(
+                variant.span(),
+                "`extra_field` is only valid on structs with named fields",
+            ));
+        } else if [&flags.variant_flags, &flags.all_fields_flags]
+            .into_iter()
+            .flatten()
+            .any(|flags| flags.max_depth.is_some())
+        {
+            return Err(syn::Error::new(
+                variant.span(),
+                "`max_depth` is only valid on the enum itself, not its variants",
+            ));
+        }
+
         // If there's top level flags, apply them to the variant's flags if they're not already set.
         if flags.variant_flags.is_none() {
-            if let Some(top_level_flags) = top_level_flags {
-                flags.variant_flags = Some(top_level_flags);
-            }
+            flags.variant_flags.clone_from(&top_level_flags);
+        }
+        if flags.all_fields_flags.is_none() && !matches!(variant.fields, syn::Fields::Unit) {
+            flags.all_fields_flags.clone_from(&top_level_fields_flags);
         }
 
         variant_flags.push(flags);
     }
 
+    #[cfg(feature = "strict")]
+    if top_level_flags.is_some()
+        && !variant_flags.is_empty()
+        && variant_flags
+            .iter()
+            .all(|flags| matches!(flags.variant_flags, Some(FieldFlags { skip: true, .. })))
+    {
+        return Err(syn::Error::new(
+            attrs[0].span(),
+            "`#[redact(all, variant)]` has no effect because every variant is marked as `#[redact(variant, skip)]`",
+        ));
+    }
+
     // Create an iterator that will yield variant names as an identifier.
     // We'll use this to match on the variants in the Debug impl.
     let variant_idents = e.variants.iter().map(|variant| &variant.ident);
@@ -143,6 +230,7 @@ pub(super) fn derive_redact(
     // Create an iterator that will yield tokens that destructure an enum variant into its respective fields.
     // Struct variant fields are destructed as normal.
     // Tuple variant fields are destructed as arg0, arg1, ... argN.
+    // Every field gets a binding, even skipped ones, so that the indices agree with `FormatData::impl_debug`.
     // Unit variants yield no tokens.
     let variant_destructures = e.variants.iter().map(|variant| match &variant.fields {
         syn::Fields::Named(syn::FieldsNamed { named, .. }) => {
@@ -163,18 +251,20 @@ pub(super) fn derive_redact(
     // Create an iterator that will yield the tokens of the body of the match arm for each variant.
     // These match arm bodies will actually print data into the Formatter.
     let mut variant_bodies = Vec::with_capacity(e.variants.len());
-    for (variant, flags) in e.variants.iter().zip(variant_flags.into_iter()) {
+    for (variant, flags) in e.variants.iter().zip(variant_flags) {
+        let variant_path = format!("{name_ident}::{}", variant.ident);
+
         // Variant name redacting
         let variant_name = variant.ident.to_string();
         let variant_name = if let Some(flags @ FieldFlags { skip: false, .. }) = &flags.variant_flags {
             // The variant name must always be formatted with the Display impl.
             let flags = FieldFlags {
                 display: true,
-                ..*flags
+                ..flags.clone()
             };
 
             // Generate the RedactionFormatter expression for the variant name
-            let redact = fmt::generate_redact_call(quote! { &#variant_name }, false, &flags, unused);
+            let redact = fmt::generate_redact_call(quote! { &#variant_name }, &variant_name, None, &flags, unused);
 
             // Because the other side is expecting a &str, we need to convert the RedactionFormatter to a String (and then to a &str)
             quote! { format!("{:?}", #redact).as_str() }
@@ -183,12 +273,22 @@ pub(super) fn derive_redact(
         };
 
         variant_bodies.push(match &variant.fields {
-            syn::Fields::Named(named) => {
-                FormatData::FieldsNamed(named).impl_debug(variant_name, flags.all_fields_flags, false, unused)?
-            }
-            syn::Fields::Unnamed(unnamed) => {
-                FormatData::FieldsUnnamed(unnamed).impl_debug(variant_name, flags.all_fields_flags, false, unused)?
-            }
+            syn::Fields::Named(named) => FormatData::FieldsNamed(named).impl_debug(
+                variant_name,
+                &variant_path,
+                flags.all_fields_flags,
+                &[],
+                FieldAccess::Bindings,
+                unused,
+            )?,
+            syn::Fields::Unnamed(unnamed) => FormatData::FieldsUnnamed(unnamed).impl_debug(
+                variant_name,
+                &variant_path,
+                flags.all_fields_flags,
+                &[],
+                FieldAccess::Bindings,
+                unused,
+            )?,
             syn::Fields::Unit => {
                 if flags.all_fields_flags.is_some() {
                     return Err(syn::Error::new(
@@ -202,10 +302,14 @@ pub(super) fn derive_redact(
         });
     }
 
+    let depth_guard = fmt::generate_depth_guard(max_depth);
+
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     Ok(quote! {
         impl #impl_generics ::std::fmt::Debug for #name_ident #ty_generics #where_clause {
             fn fmt(&self, fmt: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                #depth_guard
+
                 #[allow(unused)] // Suppresses unused warning with `#[redact(display)]`
                 let alternate = fmt.alternate();
 
@@ -1,8 +1,11 @@
+use quote::ToTokens;
 use std::num::NonZeroU8;
-use syn::{spanned::Spanned, LitChar, LitInt};
+use syn::{spanned::Spanned, LitInt, LitStr};
 
 pub struct FieldFlagsParse {
     pub skip_allowed: bool,
+    pub transparent_allowed: bool,
+    pub no_display_allowed: bool,
 }
 
 pub enum ParseMeta {
@@ -12,7 +15,12 @@ pub enum ParseMeta {
 
 type TryParseMeta = Result<ParseMeta, syn::Error>;
 
-pub trait ExtractFlags: Sized + Copy + Default {
+/// The character to redact with unless `with` is given, set by the `profile-*` features.
+///
+/// Must match `veil::private::RedactFlags::DEFAULT_REDACT_CHAR`.
+const DEFAULT_REDACT_CHAR: char = if cfg!(feature = "profile-strict") { '#' } else { '*' };
+
+pub trait ExtractFlags: Sized + Clone + Default {
     type Options;
 
     fn try_parse_meta(&mut self, meta: &mut syn::meta::ParseNestedMeta) -> TryParseMeta;
@@ -33,7 +41,7 @@ pub trait ExtractFlags: Sized + Copy + Default {
         attrs: &[syn::Attribute],
         options: Self::Options,
     ) -> Result<[Option<Self>; AMOUNT], syn::Error> {
-        let mut extracted = [None; AMOUNT];
+        let mut extracted = std::array::from_fn(|_| None);
         let mut head = 0;
 
         for attr in attrs {
@@ -99,18 +107,120 @@ impl quote::ToTokens for RedactionLength {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+/// The predicate of a `#[redact(cfg(...))]` modifier, e.g. `feature = "prod-logging"`.
+#[derive(Clone)]
+pub struct CfgPredicate(pub proc_macro2::TokenStream);
+impl PartialEq for CfgPredicate {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_string() == other.0.to_string()
+    }
+}
+impl Eq for CfgPredicate {}
+impl quote::ToTokens for CfgPredicate {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        self.0.to_tokens(tokens)
+    }
+}
+
+/// A path given to a modifier, such as `#[redact(policy = PATH)]` or `#[redact(with_fn_ctx = PATH)]`.
+#[derive(Clone)]
+pub struct FlagPath(pub syn::Path);
+impl PartialEq for FlagPath {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_token_stream().to_string() == other.0.to_token_stream().to_string()
+    }
+}
+impl Eq for FlagPath {}
+
+#[derive(Clone, PartialEq, Eq)]
 pub struct RedactFlags {
     pub redact_length: RedactionLength,
 
-    /// The character to use for redacting. Defaults to `*`.
-    pub redact_char: char,
+    /// The character to use for redacting, from `#[redact(with = '...')]`. Defaults to `*`, or the default of the
+    /// enabled `profile-*` feature.
+    ///
+    /// Kept as given, so that modifiers that clash with `with` are rejected even if it's the default character.
+    pub redact_char: Option<char>,
+
+    /// The characters to use for redacting, from `#[redact(with = "...")]`. Requires `cycle`.
+    pub redact_pattern: Option<String>,
+
+    /// Whether to cycle through the characters of `redact_pattern` across the redacted characters.
+    pub cycle: bool,
+
+    /// Whether to only redact digits, leaving everything else as-is.
+    pub digits_only: bool,
+
+    /// Whether partial redaction only exposes the first character of CJK names. Requires `partial`.
+    pub cjk: bool,
+
+    /// The percentage of the data to expose at each end of a partial redaction, instead of the default.
+    pub expose_pct: Option<u8>,
+
+    /// The most characters to expose at each end of a partial redaction, instead of the default.
+    pub expose: Option<NonZeroU8>,
+
+    /// The minimum number of alphanumeric characters for data to be partially redacted, instead of the default.
+    pub min_partial_chars: Option<NonZeroU8>,
+
+    /// Whether partial redaction only exposes the beginning of the data. Requires `partial`.
+    pub expose_start: bool,
+
+    /// Whether partial redaction only exposes the end of the data. Requires `partial`.
+    pub expose_end: bool,
+
+    /// The characters to redact letters and digits with, from `#[redact(preserve_class)]`.
+    pub preserve_class: Option<(char, char)>,
+
+    /// How many characters to redact at the beginning of the data, exposing the middle.
+    pub mask_prefix: Option<u8>,
+
+    /// How many characters to redact at the end of the data, exposing the middle.
+    pub mask_suffix: Option<u8>,
+
+    /// The width to pad the redacted output to with spaces.
+    pub pad: Option<NonZeroU8>,
+
+    /// How to align the redacted output within `pad`, from `#[redact(align = "...")]`. Defaults to the left.
+    pub align: Option<Alignment>,
+
+    /// The algorithm to replace the data with the digest of, from `#[redact(hash = "...")]`.
+    pub hash: Option<HashAlgo>,
+}
+
+/// The alignment of padded output, from `#[redact(align = "...")]`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Alignment {
+    Left,
+    Right,
+    Center,
+}
+
+/// The hash algorithm, from `#[redact(hash = "...")]`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum HashAlgo {
+    Sha256,
 }
 impl Default for RedactFlags {
     fn default() -> Self {
         Self {
             redact_length: RedactionLength::Full,
-            redact_char: '*',
+            redact_char: None,
+            redact_pattern: None,
+            cycle: false,
+            digits_only: false,
+            cjk: false,
+            expose_pct: None,
+            expose: None,
+            min_partial_chars: None,
+            expose_start: false,
+            expose_end: false,
+            preserve_class: None,
+            mask_prefix: None,
+            mask_suffix: None,
+            pad: None,
+            align: None,
+            hash: None,
         }
     }
 }
@@ -124,47 +234,429 @@ impl ExtractFlags for RedactFlags {
                 return TryParseMeta::Err(meta.error("`partial` clashes with an existing redaction length flag"));
             }
             self.redact_length = RedactionLength::Partial;
-        // #[redact(with = 'X')]
+        // #[redact(with = 'X')] or #[redact(with = "XY", cycle)]
         } else if meta.path.is_ident("with") {
-            let ch: LitChar = meta.value()?.parse()?;
-            self.redact_char = ch.value();
-            // #[redact(fixed = u8)]
+            match meta.value()?.parse()? {
+                syn::Lit::Char(ch) => self.redact_char = Some(ch.value()),
+                syn::Lit::Str(pattern) if pattern.value().is_empty() => {
+                    return Err(syn::Error::new(pattern.span(), "`with` can't be an empty string"));
+                }
+                syn::Lit::Str(pattern) => self.redact_pattern = Some(pattern.value()),
+                lit => return Err(syn::Error::new(lit.span(), "expected a `char` or a string")),
+            }
+        // #[redact(cycle)]
+        } else if meta.path.is_ident("cycle") {
+            self.cycle = true;
+        // #[redact(digits_only)]
+        } else if meta.path.is_ident("digits_only") {
+            self.digits_only = true;
+        // #[redact(cjk)]
+        } else if meta.path.is_ident("cjk") {
+            self.cjk = true;
+        // #[redact(preserve_class)] or #[redact(preserve_class = "x0")]
+        } else if meta.path.is_ident("preserve_class") {
+            self.preserve_class = Some(('x', '0'));
+            if meta.input.peek(syn::Token![=]) {
+                let chars: syn::LitStr = meta.value()?.parse()?;
+                let [letter, digit] = chars.value().chars().collect::<Vec<_>>()[..] else {
+                    return Err(syn::Error::new(
+                        chars.span(),
+                        "`preserve_class` expects two characters, the one to redact letters with and the one to redact digits with",
+                    ));
+                };
+                self.preserve_class = Some((letter, digit));
+            }
+        // #[redact(expose_pct = u8)]
+        } else if meta.path.is_ident("expose_pct") {
+            let int: LitInt = meta.value()?.parse()?;
+            let pct = int.base10_parse::<u8>()?;
+            if pct > 49 {
+                return Err(syn::Error::new(
+                    int.span(),
+                    "`expose_pct` must be between 0 and 49, as half or more of the data would be exposed",
+                ));
+            }
+            self.expose_pct = Some(pct);
+        // #[redact(expose = u8)]
+        } else if meta.path.is_ident("expose") {
+            let int: LitInt = meta.value()?.parse()?;
+            self.expose = Some(NonZeroU8::new(int.base10_parse::<u8>()?).ok_or_else(|| {
+                syn::Error::new(
+                    int.span(),
+                    "`expose` must be greater than zero, use `#[redact]` to expose nothing",
+                )
+            })?);
+        // #[redact(expose_start)]
+        } else if meta.path.is_ident("expose_start") {
+            self.expose_start = true;
+        // #[redact(expose_end)]
+        } else if meta.path.is_ident("expose_end") {
+            self.expose_end = true;
+        // #[redact(min = u8)]
+        } else if meta.path.is_ident("min") {
+            let int: LitInt = meta.value()?.parse()?;
+            self.min_partial_chars = Some(
+                NonZeroU8::new(int.base10_parse::<u8>()?)
+                    .ok_or_else(|| syn::Error::new(int.span(), "`min` must be greater than zero"))?,
+            );
+        // #[redact(mask_prefix = u8)]
+        } else if meta.path.is_ident("mask_prefix") {
+            let int: LitInt = meta.value()?.parse()?;
+            self.mask_prefix = Some(int.base10_parse::<u8>()?);
+        // #[redact(mask_suffix = u8)]
+        } else if meta.path.is_ident("mask_suffix") {
+            let int: LitInt = meta.value()?.parse()?;
+            self.mask_suffix = Some(int.base10_parse::<u8>()?);
+        // #[redact(pad = u8)]
+        } else if meta.path.is_ident("pad") {
+            let int: LitInt = meta.value()?.parse()?;
+            self.pad = Some(
+                NonZeroU8::new(int.base10_parse::<u8>()?)
+                    .ok_or_else(|| syn::Error::new(int.span(), "`pad` width must be greater than zero"))?,
+            );
+        // #[redact(align = "left" | "right" | "center")]
+        } else if meta.path.is_ident("align") {
+            let align: LitStr = meta.value()?.parse()?;
+            self.align = Some(match align.value().as_str() {
+                "left" => Alignment::Left,
+                "right" => Alignment::Right,
+                "center" => Alignment::Center,
+                _ => {
+                    return Err(syn::Error::new(
+                        align.span(),
+                        "unknown alignment, `align` only supports `left`, `right` and `center`",
+                    ))
+                }
+            });
+        // #[redact(hash = "sha256")]
+        } else if meta.path.is_ident("hash") {
+            let algo: LitStr = meta.value()?.parse()?;
+            self.hash = Some(match algo.value().as_str() {
+                "sha256" => HashAlgo::Sha256,
+                _ => {
+                    return Err(syn::Error::new(
+                        algo.span(),
+                        "unknown hash algorithm, `hash` only supports `sha256`",
+                    ))
+                }
+            });
+        // #[redact(fixed = u8)]
         } else if meta.path.is_ident("fixed") {
             if self.redact_length != RedactionLength::Full {
                 return TryParseMeta::Err(meta.error("`fixed` clashes with an existing redaction length flag"));
             }
             let int: LitInt = meta.value()?.parse()?;
-            self.redact_length = RedactionLength::Fixed(
-                match int.base10_parse::<u8>().and_then(|int| {
-                    NonZeroU8::new(int)
-                        .ok_or_else(|| syn::Error::new_spanned(int, "fixed redacting width must be greater than zero"))
-                }) {
-                    Ok(fixed) => fixed,
-                    Err(err) => return TryParseMeta::Err(err),
-                },
-            )
+            self.redact_length = RedactionLength::Fixed(int.base10_parse::<u8>().and_then(|int| {
+                NonZeroU8::new(int)
+                    .ok_or_else(|| syn::Error::new_spanned(int, "fixed redacting width must be greater than zero"))
+            })?)
         } else {
             return Ok(ParseMeta::Unrecognised);
         }
         Ok(ParseMeta::Consumed)
     }
+
+    fn validate(&self, attr: &syn::Attribute, _options: &Self::Options) -> Result<(), syn::Error> {
+        if self.hash.is_some() {
+            if self.redact_length != RedactionLength::Full
+                || self.mask_prefix.is_some()
+                || self.mask_suffix.is_some()
+                || self.redact_char.is_some()
+                || self.redact_pattern.is_some()
+                || self.digits_only
+                || self.preserve_class.is_some()
+            {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "`hash` replaces all of the data with its digest, so it can't be combined with `partial`, `fixed`, `mask_prefix`, `mask_suffix`, `with`, `digits_only` or `preserve_class`",
+                ));
+            } else if !cfg!(feature = "hash") {
+                return Err(syn::Error::new(attr.span(), "`hash` requires veil's `hash` feature"));
+            }
+        }
+
+        if self.expose_pct.is_some() && self.redact_length != RedactionLength::Partial {
+            return Err(syn::Error::new(attr.span(), "`expose_pct` requires `partial`"));
+        }
+
+        if self.expose.is_some() {
+            if self.redact_length != RedactionLength::Partial {
+                return Err(syn::Error::new(attr.span(), "`expose` requires `partial`"));
+            } else if self.expose_pct.is_some() {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "`expose` can't be combined with `expose_pct`",
+                ));
+            }
+        }
+
+        if self.expose_start || self.expose_end {
+            if self.redact_length != RedactionLength::Partial {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "`expose_start` and `expose_end` require `partial`",
+                ));
+            } else if self.expose_start && self.expose_end {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "`expose_start` and `expose_end` can't be combined, use just `partial` to expose both ends",
+                ));
+            } else if self.cjk {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "`cjk` can't be combined with `expose_start` or `expose_end`, it always exposes the first character",
+                ));
+            }
+        }
+
+        if let Some(min) = self.min_partial_chars {
+            if self.redact_length != RedactionLength::Partial {
+                return Err(syn::Error::new(attr.span(), "`min` requires `partial`"));
+            } else if self
+                .expose
+                .is_some_and(|expose| min.get() < expose.get().saturating_mul(2))
+            {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "`min` must be at least twice `expose`, so that data is never exposed entirely",
+                ));
+            }
+        }
+
+        if self.cjk && self.redact_length != RedactionLength::Partial {
+            return Err(syn::Error::new(attr.span(), "`cjk` requires `partial`"));
+        }
+
+        if self.align.is_some() && self.pad.is_none() {
+            return Err(syn::Error::new(attr.span(), "`align` requires `pad`"));
+        }
+
+        if (self.mask_prefix.is_some() || self.mask_suffix.is_some()) && self.redact_length != RedactionLength::Full {
+            return Err(syn::Error::new(
+                attr.span(),
+                "`mask_prefix` and `mask_suffix` can't be combined with `partial` or `fixed`",
+            ));
+        }
+
+        if self.preserve_class.is_some() {
+            if matches!(self.redact_length, RedactionLength::Fixed(_)) {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "`preserve_class` can't be combined with `fixed`, as none of the data is shown",
+                ));
+            } else if self.redact_char.is_some() || self.redact_pattern.is_some() {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "`preserve_class` can't be combined with `with`, it decides which characters to redact with",
+                ));
+            }
+        }
+
+        if self.redact_pattern.is_some() && !self.cycle {
+            return Err(syn::Error::new(
+                attr.span(),
+                "a string `with` requires `cycle`, use a `char` such as `with = 'X'` otherwise",
+            ));
+        } else if self.cycle && self.redact_pattern.is_none() {
+            return Err(syn::Error::new(
+                attr.span(),
+                "`cycle` requires `with` to be a string of the characters to cycle through",
+            ));
+        }
+
+        Ok(())
+    }
 }
 impl quote::ToTokens for RedactFlags {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let Self {
             redact_length,
             redact_char,
-            ..
+            redact_pattern,
+            cycle: _,
+            digits_only,
+            cjk,
+            expose_pct,
+            expose,
+            min_partial_chars,
+            expose_start,
+            expose_end,
+            preserve_class,
+            mask_prefix,
+            mask_suffix,
+            pad,
+            align,
+            hash,
         } = self;
 
+        let pad = match pad {
+            Some(width) => {
+                let width = width.get();
+                let align = match align.unwrap_or(Alignment::Left) {
+                    Alignment::Left => quote! { ::std::fmt::Alignment::Left },
+                    Alignment::Right => quote! { ::std::fmt::Alignment::Right },
+                    Alignment::Center => quote! { ::std::fmt::Alignment::Center },
+                };
+                quote! { ::std::option::Option::Some(veil::private::Padding { width: #width, align: #align }) }
+            }
+            None => quote! { ::std::option::Option::None },
+        };
+
+        let redact_cycle = match redact_pattern {
+            Some(pattern) => quote! { ::std::option::Option::Some(#pattern) },
+            None => quote! { ::std::option::Option::None },
+        };
+
+        let expose_side = match (expose_start, expose_end) {
+            (true, _) => quote! { ::std::option::Option::Some(veil::private::ExposeSide::Start) },
+            (_, true) => quote! { ::std::option::Option::Some(veil::private::ExposeSide::End) },
+            _ => quote! { ::std::option::Option::None },
+        };
+
+        let min_partial_chars = match min_partial_chars {
+            Some(min) => {
+                let min = min.get();
+                quote! { ::std::option::Option::Some(::core::num::NonZeroU8::new(#min).unwrap()) }
+            }
+            None => quote! { ::std::option::Option::None },
+        };
+
+        let preserve_class = match preserve_class {
+            Some((letter, digit)) => quote! {
+                ::std::option::Option::Some(veil::private::PreserveClass { letter: #letter, digit: #digit })
+            },
+            None => quote! { ::std::option::Option::None },
+        };
+
+        let hash = match hash {
+            Some(HashAlgo::Sha256) => quote! { ::std::option::Option::Some(veil::private::HashAlgo::Sha256) },
+            None => quote! { ::std::option::Option::None },
+        };
+
+        let redact_length = match (redact_length, expose_pct, expose) {
+            (RedactionLength::Partial, Some(pct), _) => quote! { veil::private::RedactionLength::PartialPercent(#pct) },
+            (RedactionLength::Partial, _, Some(max)) => {
+                let max = max.get();
+                quote! { veil::private::RedactionLength::PartialExpose(#max) }
+            }
+            _ if mask_prefix.is_some() || mask_suffix.is_some() => {
+                let prefix = mask_prefix.unwrap_or(0);
+                let suffix = mask_suffix.unwrap_or(0);
+                quote! { veil::private::RedactionLength::MaskEnds { prefix: #prefix, suffix: #suffix } }
+            }
+            _ => redact_length.to_token_stream(),
+        };
+
+        let redact_char = redact_char.unwrap_or(DEFAULT_REDACT_CHAR);
         tokens.extend(quote! {
             redact_length: #redact_length,
-            redact_char: #redact_char
+            redact_char: #redact_char,
+            redact_cycle: #redact_cycle,
+            digits_only: #digits_only,
+            cjk: #cjk,
+            preserve_class: #preserve_class,
+            pad: #pad,
+            min_mask_len: ::std::option::Option::None,
+            min_partial_chars: #min_partial_chars,
+            expose_side: #expose_side,
+            secret: false,
+            hash: #hash
         });
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Default)]
+/// A specialization detected from the field's type, or requested with `#[redact(as = "...")]` for when it can't be.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Specialization {
+    Option,
+    Error,
+    Vec,
+
+    /// Maps only get their own specialization with `#[redact(values)]`, otherwise their keys are redacted too.
+    Map,
+
+    /// Strings are redacted straight from their borrowed `&str`. Only ever detected from the type.
+    Str,
+
+    /// Numeric wrappers such as `Wrapping<T>` are redacted as the number they wrap. Only ever detected from the type.
+    NumWrapper,
+}
+
+/// A computed field added to a struct's output with `#[redact(extra_field(...))]`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ExtraField {
+    /// The name the field is shown under.
+    pub name: String,
+
+    /// The method on `self` that returns the field's value.
+    pub method: syn::Ident,
+
+    /// How the value is redacted.
+    pub flags: FieldFlags,
+}
+impl ExtraField {
+    fn parse(meta: &syn::meta::ParseNestedMeta) -> Result<Self, syn::Error> {
+        let mut name = None;
+        let mut method = None;
+        let mut flags = FieldFlags::default();
+
+        meta.parse_nested_meta(|mut meta| {
+            // name = "..."
+            if meta.path.is_ident("name") {
+                name = Some(meta.value()?.parse::<LitStr>()?.value());
+            }
+            // source = "self.method"
+            else if meta.path.is_ident("source") {
+                method = Some(parse_self_method(
+                    &meta.value()?.parse()?,
+                    "`source` must name a method on `self`, e.g. `self.computed`",
+                )?);
+            } else {
+                flags.parse_meta("Redact", &mut meta)?;
+            }
+            Ok(())
+        })?;
+
+        let (Some(name), Some(method)) = (name, method) else {
+            return Err(syn::Error::new(
+                meta.path.span(),
+                "`extra_field` requires both `name` and `source`",
+            ));
+        };
+
+        // Only modifiers that change how the value is redacted make sense for a computed value.
+        let valid_flags = FieldFlags {
+            display: flags.display,
+            catch: flags.catch,
+            redact: flags.redact.clone(),
+            ..Default::default()
+        };
+        if flags != valid_flags {
+            return Err(syn::Error::new(
+                meta.path.span(),
+                "`extra_field` only accepts `display`, `catch` and redaction modifiers",
+            ));
+        }
+
+        Ok(Self { name, method, flags })
+    }
+}
+
+/// Parses a method on `self` given as a string, e.g. `"self.computed"`, pointing the method's name at the string.
+fn parse_self_method(source: &LitStr, error: &str) -> Result<syn::Ident, syn::Error> {
+    match source.parse::<syn::Expr>() {
+        Ok(syn::Expr::Field(syn::ExprField {
+            base,
+            member: syn::Member::Named(ident),
+            ..
+        })) if matches!(&*base, syn::Expr::Path(path) if path.path.is_ident("self")) => {
+            Ok(syn::Ident::new(&ident.to_string(), source.span()))
+        }
+        _ => Err(syn::Error::new(source.span(), error)),
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Default)]
 pub struct FieldFlags {
     /// Whether to blanket redact everything (fields, variants)
     pub all: bool,
@@ -182,9 +674,110 @@ pub struct FieldFlags {
     /// Whether to use the type's [`Display`](std::fmt::Display) implementation instead of [`Debug`].
     pub display: bool,
 
+    /// Uses the type's [`Debug`] implementation even though the container is marked as `#[redact(all, display)]`.
+    ///
+    /// Only allowed if this field is affected by a `#[redact(all, display)]` attribute.
+    pub no_display: bool,
+
+    /// Whether to use the type's own [`Redactable`](https://docs.rs/veil/latest/veil/trait.Redactable.html) implementation.
+    pub redactable: bool,
+
+    /// Redact the field even if its type is already redacted by another crate, such as `secrecy`.
+    pub force: bool,
+
+    /// Shows the field under a different name.
+    ///
+    /// Only allowed on named struct fields.
+    pub rename: Option<String>,
+
+    /// Moves the field within the output. Fields are shown in ascending order, where fields without it count as `0`.
+    ///
+    /// Fields with the same order keep their declaration order. Only allowed on fields.
+    pub order: Option<i32>,
+
+    /// Formats the struct as its single field, without the struct's name.
+    ///
+    /// Only allowed on structs.
+    pub transparent: bool,
+
+    /// Formats the struct by redacting the [`Debug`] output of its [`Deref`](std::ops::Deref) target instead of its fields.
+    ///
+    /// Only allowed on structs.
+    pub deref_transparent: bool,
+
+    /// Formats the struct as its name and a fixed redaction marker, e.g. `CreditCard(*)`, without formatting any fields.
+    ///
+    /// Only allowed on structs.
+    pub opaque: bool,
+
+    /// Only redacts the field when this `cfg` predicate is active, otherwise it is shown as-is.
+    ///
+    /// Resolved at compile time.
+    pub cfg: Option<CfgPredicate>,
+
+    /// Shows the field's type, e.g. `<String>`, instead of its redacted value.
+    pub type_only: bool,
+
+    /// Writes a placeholder instead of panicking if the field's formatting panics.
+    pub catch: bool,
+
+    /// Formats the field with a method on it that returns a `Result`, redacting its `Ok` value with [`Display`] and
+    /// `<unformattable>` in place of an `Err`.
+    ///
+    /// Only allowed on fields.
+    pub try_display: Option<syn::Ident>,
+
+    /// Reads the redaction flags from a shared `Redactor` instead of `redact`.
+    ///
+    /// Points to a `const` or `static` `veil::redactor::Redactor`.
+    pub policy: Option<FlagPath>,
+
+    /// Redacts the field with a function that also receives the field's name and a `Redactor` with the configured flags.
+    pub with_fn_ctx: Option<FlagPath>,
+
+    /// Applies a specialization even though it wasn't detected from the field's type, e.g. because it's a type alias.
+    pub specialization: Option<Specialization>,
+
+    /// Redacts `None` too, as a fixed redaction marker that `Some` is redacted as as well, so the output doesn't show
+    /// whether the value is present.
+    ///
+    /// Only allowed on `Option<T>` fields.
+    pub mask_none: bool,
+
+    /// Keeps redacting the field even when redaction has been disabled at runtime with the `toggle` feature.
+    pub secret: bool,
+
+    /// Only redacts the values of a map, keeping its keys as-is.
+    ///
+    /// Only allowed on `HashMap<K, V>` and `BTreeMap<K, V>` fields, or with `#[redact(as = "Map")]`.
+    pub values: bool,
+
+    /// Types that are trusted to redact themselves, so `#[redact(all)]` shows fields of these types with their own
+    /// [`Debug`] implementation instead of redacting them.
+    ///
+    /// Types are compared by their tokens, e.g. `Vec<Vehicle>`, with any references on the field's type removed.
+    pub trust_inner: Vec<String>,
+
+    /// Cuts off the type's [`Debug`] output with `...` where it's nested within itself more than this many times, e.g.
+    /// in a tree of `Box<Self>` children.
+    ///
+    /// Only allowed on structs and enums.
+    pub max_depth: Option<NonZeroU8>,
+
+    /// Computed fields to add to the output after the struct's own fields.
+    ///
+    /// Only allowed on structs with named fields.
+    pub extra_fields: Vec<ExtraField>,
+
     /// Flags that modify the redaction behavior.
     pub redact: RedactFlags,
 }
+impl FieldFlags {
+    /// Whether `display` is combined with `fixed`, which ignores the data entirely, so `display` has no effect.
+    pub fn is_display_ignored(&self) -> bool {
+        self.display && (self.mask_none || matches!(self.redact.redact_length, RedactionLength::Fixed(_)))
+    }
+}
 impl ExtractFlags for FieldFlags {
     type Options = FieldFlagsParse;
 
@@ -209,6 +802,120 @@ impl ExtractFlags for FieldFlags {
             self.variant = true;
         } else if meta.path.is_ident("display") {
             self.display = true;
+        }
+        // #[redact(no_display)]
+        else if meta.path.is_ident("no_display") {
+            self.no_display = true;
+        }
+        // #[redact(redactable)]
+        else if meta.path.is_ident("redactable") {
+            self.redactable = true;
+        }
+        // #[redact(force)]
+        else if cfg!(feature = "secrecy") && meta.path.is_ident("force") {
+            self.force = true;
+        }
+        // #[redact(rename = "...")]
+        else if meta.path.is_ident("rename") {
+            let name: LitStr = meta.value()?.parse()?;
+            self.rename = Some(name.value());
+        }
+        // #[redact(order = N)]
+        else if meta.path.is_ident("order") {
+            let order: LitInt = meta.value()?.parse()?;
+            self.order = Some(order.base10_parse()?);
+        }
+        // #[redact(max_depth = N)]
+        else if meta.path.is_ident("max_depth") {
+            let int: LitInt = meta.value()?.parse()?;
+            self.max_depth = Some(
+                NonZeroU8::new(int.base10_parse::<u8>()?)
+                    .ok_or_else(|| syn::Error::new(int.span(), "`max_depth` must be greater than zero"))?,
+            );
+        }
+        // #[redact(as = "...")]
+        else if meta.path.is_ident("as") {
+            let specialization: LitStr = meta.value()?.parse()?;
+            self.specialization = match specialization.value().as_str() {
+                "Option" => Some(Specialization::Option),
+                "Error" => Some(Specialization::Error),
+                "Vec" => Some(Specialization::Vec),
+                "Map" => Some(Specialization::Map),
+                _ => {
+                    return Err(syn::Error::new(
+                        specialization.span(),
+                        "unknown specialization, `as` only supports `Option`, `Error`, `Vec` and `Map`",
+                    ))
+                }
+            };
+        }
+        // #[redact(values)]
+        else if meta.path.is_ident("values") {
+            self.values = true;
+        }
+        // #[redact(mask_none)]
+        else if meta.path.is_ident("mask_none") {
+            self.mask_none = true;
+        }
+        // #[redact(secret)]
+        else if meta.path.is_ident("secret") {
+            self.secret = true;
+        }
+        // #[redact(trust_inner(Type, ...))]
+        else if meta.path.is_ident("trust_inner") {
+            let types;
+            syn::parenthesized!(types in meta.input);
+            let types = types.parse_terminated(<syn::Type as syn::parse::Parse>::parse, syn::Token![,])?;
+            if types.is_empty() {
+                return Err(meta.error("`trust_inner` expects at least one type, e.g. `trust_inner(CreditCard)`"));
+            }
+            self.trust_inner
+                .extend(types.iter().map(|ty| ty.to_token_stream().to_string()));
+        }
+        // #[redact(extra_field(name = "...", source = "self.method", ...))]
+        else if meta.path.is_ident("extra_field") {
+            self.extra_fields.push(ExtraField::parse(meta)?);
+        }
+        // #[redact(transparent)]
+        else if meta.path.is_ident("transparent") {
+            self.transparent = true;
+        }
+        // #[redact(deref_transparent)]
+        else if meta.path.is_ident("deref_transparent") {
+            self.deref_transparent = true;
+        }
+        // #[redact(opaque)]
+        else if meta.path.is_ident("opaque") {
+            self.opaque = true;
+        }
+        // #[redact(cfg(...))]
+        else if meta.path.is_ident("cfg") {
+            let predicate;
+            syn::parenthesized!(predicate in meta.input);
+            self.cfg = Some(CfgPredicate(predicate.parse()?));
+        }
+        // #[redact(type_only)]
+        else if meta.path.is_ident("type_only") {
+            self.type_only = true;
+        }
+        // #[redact(catch)]
+        else if meta.path.is_ident("catch") {
+            self.catch = true;
+        }
+        // #[redact(try_display = "self.method")]
+        else if meta.path.is_ident("try_display") {
+            self.try_display = Some(parse_self_method(
+                &meta.value()?.parse()?,
+                "`try_display` must name a method on `self`, e.g. `self.fmt_safe`",
+            )?);
+        }
+        // #[redact(policy = PATH)]
+        else if meta.path.is_ident("policy") {
+            self.policy = Some(FlagPath(meta.value()?.parse()?));
+        }
+        // #[redact(with_fn_ctx = PATH)]
+        else if meta.path.is_ident("with_fn_ctx") {
+            self.with_fn_ctx = Some(FlagPath(meta.value()?.parse()?));
         } else {
             return Ok(ParseMeta::Unrecognised);
         }
@@ -217,16 +924,184 @@ impl ExtractFlags for FieldFlags {
     }
 
     fn validate(&self, attr: &syn::Attribute, options: &Self::Options) -> Result<(), syn::Error> {
+        self.redact.validate(attr, &())?;
+
+        if self.transparent && !options.transparent_allowed {
+            return Err(syn::Error::new(
+                attr.span(),
+                "`#[redact(transparent)]` is only valid on structs",
+            ));
+        } else if self.deref_transparent && !options.transparent_allowed {
+            return Err(syn::Error::new(
+                attr.span(),
+                "`#[redact(deref_transparent)]` is only valid on structs",
+            ));
+        } else if self.opaque && !options.transparent_allowed {
+            return Err(syn::Error::new(
+                attr.span(),
+                "`#[redact(opaque)]` is only valid on structs",
+            ));
+        }
+
+        if self.no_display {
+            if !options.no_display_allowed {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "`#[redact(no_display)]` is only allowed on fields of a `#[redact(all, display)]` container",
+                ));
+            } else if self.display {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "`#[redact(no_display)]` can't be combined with `display`",
+                ));
+            }
+        }
+
+        // The type's own `Redactable` implementation decides how it's redacted, so other modifiers would be ignored.
+        if self.redactable
+            && (self.variant
+                || self.display
+                || self.no_display
+                || self.force
+                || self.catch
+                || self.policy.is_some()
+                || self.with_fn_ctx.is_some()
+                || self.type_only
+                || self.specialization.is_some()
+                || self.mask_none
+                || self.secret
+                || self.redact != RedactFlags::default())
+        {
+            return Err(syn::Error::new(
+                attr.span(),
+                "`#[redact(redactable)]` can't be combined with other redaction modifiers",
+            ));
+        }
+
+        // Nothing of the value is shown, so any modifier that changes how it's redacted would be ignored.
+        if self.type_only
+            && (self.variant
+                || self.display
+                || self.no_display
+                || self.policy.is_some()
+                || self.with_fn_ctx.is_some()
+                || self.specialization.is_some()
+                || self.mask_none
+                || self.secret
+                || self.redact != RedactFlags::default())
+        {
+            return Err(syn::Error::new(
+                attr.span(),
+                "`#[redact(type_only)]` can't be combined with modifiers that change how the value is redacted",
+            ));
+        }
+
+        // Outside of `strict`, this is only a warning, see `generate_display_ignored_warning`.
+        #[cfg(feature = "strict")]
+        if self.is_display_ignored() {
+            return Err(syn::Error::new(
+                attr.span(),
+                "`display` has no effect with `fixed`, the data is always redacted as a fixed number of characters",
+            ));
+        }
+
+        // The policy decides how the data is redacted, so these would be ignored.
+        if self.policy.is_some() && self.redact != RedactFlags::default() {
+            return Err(syn::Error::new(
+                attr.span(),
+                "`policy` can't be combined with other redaction modifiers",
+            ));
+        }
+
+        if self.try_display.is_some() {
+            if self.all || self.variant || options.transparent_allowed {
+                return Err(syn::Error::new(attr.span(), "`try_display` is only valid on fields"));
+            } else if self.display
+                || self.redactable
+                || self.type_only
+                || self.specialization.is_some()
+                || self.mask_none
+            {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "`try_display` already decides how the field is formatted, so it can't be combined with `display`, `redactable`, `type_only`, `as` or `mask_none`",
+                ));
+            }
+        }
+
+        if self.values {
+            if self.all || self.variant || options.transparent_allowed {
+                return Err(syn::Error::new(attr.span(), "`values` is only valid on fields"));
+            } else if self.redactable
+                || self.type_only
+                || self.with_fn_ctx.is_some()
+                || self.try_display.is_some()
+                || self.mask_none
+            {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "`values` can't be combined with `redactable`, `type_only`, `with_fn_ctx`, `try_display` or `mask_none`",
+                ));
+            }
+        } else if self.specialization == Some(Specialization::Map) {
+            return Err(syn::Error::new(
+                attr.span(),
+                "`as` can only be set to `Map` with `values`, otherwise the whole map is redacted anyway",
+            ));
+        }
+
+        if !self.trust_inner.is_empty() && (!self.all || self.variant) {
+            return Err(syn::Error::new(
+                attr.span(),
+                "`trust_inner` requires `all`, and only applies to fields, not variant names",
+            ));
+        }
+
+        if self.mask_none {
+            if self.variant || self.deref_transparent {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "`mask_none` is only valid on `Option<T>` fields",
+                ));
+            }
+
+            // Anything that depends on the data would show whether it's `Some`.
+            if self.redact.redact_length == RedactionLength::Partial
+                || self.redact.mask_prefix.is_some()
+                || self.redact.mask_suffix.is_some()
+                || self.redact.preserve_class.is_some()
+                || self.policy.is_some()
+                || self.with_fn_ctx.is_some()
+            {
+                return Err(syn::Error::new(
+                    attr.span(),
+                    "`mask_none` always redacts as a fixed marker, so it can't be combined with `partial`, `mask_prefix`, `mask_suffix`, `preserve_class`, `policy` or `with_fn_ctx`",
+                ));
+            }
+        }
+
+        // Variant names are formatted separately from fields, so they can't be conditionally redacted yet.
+        if self.variant && self.cfg.is_some() {
+            return Err(syn::Error::new(
+                attr.span(),
+                "`#[redact(cfg(...))]` can't be combined with `variant`",
+            ));
+        }
+
         if self.skip {
             if !options.skip_allowed {
                 return Err(syn::Error::new(attr.span(), "`#[redact(skip)]` is not allowed here"));
             }
 
-            // It doesn't make sense for `skip` to be present with any other flags.
-            // We'll throw an error if it is.
+            // It doesn't make sense for `skip` to be present with any other flags, except for
+            // `rename` and `order` which still apply to the unredacted field, and `all` which opts an enum
+            // variant out of an enum's `#[redact(all)]`. We'll throw an error if it is.
             let valid_skip_flags = FieldFlags {
                 skip: true,
+                all: self.all,
                 variant: self.variant,
+                rename: self.rename.clone(),
+                order: self.order,
                 ..Default::default()
             };
             if self != &valid_skip_flags {
@@ -243,6 +1118,28 @@ impl ExtractFlags for FieldFlags {
 impl quote::ToTokens for FieldFlags {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         assert!(!self.skip, "internal error: skip flag should not be set here");
-        self.redact.to_tokens(tokens)
+        let flags = match &self.policy {
+            Some(FlagPath(policy)) => {
+                quote_spanned! {policy.span()=> veil::private::policy_flags(&#policy) }
+            }
+            // `Some` and `None` are both redacted as the same fixed marker, one redaction character by default.
+            None if self.mask_none && !matches!(self.redact.redact_length, RedactionLength::Fixed(_)) => {
+                let redact = RedactFlags {
+                    redact_length: RedactionLength::Fixed(NonZeroU8::MIN),
+                    ..self.redact.clone()
+                };
+                quote! { veil::private::RedactFlags { #redact } }
+            }
+            None => {
+                let redact = &self.redact;
+                quote! { veil::private::RedactFlags { #redact } }
+            }
+        };
+
+        if self.secret {
+            quote! { veil::private::RedactFlags { secret: true, ..#flags } }.to_tokens(tokens)
+        } else {
+            flags.to_tokens(tokens)
+        }
     }
 }
@@ -1,33 +1,352 @@
 use crate::{
-    flags::{ExtractFlags, FieldFlags, FieldFlagsParse},
+    flags::{ExtraField, ExtractFlags, FieldFlags, FieldFlagsParse, FlagPath, Specialization},
     redact::UnusedDiagnostic,
 };
 use quote::ToTokens;
-use syn::spanned::Spanned;
+use syn::{ext::IdentExt, spanned::Spanned};
+
+/// Strips any references from a [`syn::Type`], e.g. `&'a mut Option<T>` becomes `Option<T>`.
+///
+/// References format exactly like the value they point to, so they get the same specializations.
+fn peel_refs(ty: &syn::Type) -> &syn::Type {
+    match ty {
+        syn::Type::Reference(syn::TypeReference { elem, .. })
+        | syn::Type::Paren(syn::TypeParen { elem, .. })
+        | syn::Type::Group(syn::TypeGroup { elem, .. }) => peel_refs(elem),
+        _ => ty,
+    }
+}
 
-#[rustfmt::skip]
 /// Returns whether a [`syn::Type`] is an [`Option<T>`]
 ///
 /// We try and match as many possible paths as possible because
-/// some macros can output very verbose paths to items.
+/// some macros can output very verbose paths to items, e.g. `::core::option::Option::<T>` or `r#Option<T>`.
 fn is_ty_option(ty: &syn::Type) -> bool {
-    if let syn::Type::Path(syn::TypePath { path, .. }) = &ty {
-        match path.segments.len() {
-            1 if path.segments[0].ident == "Option" => true,
+    let syn::Type::Path(syn::TypePath { qself: None, path }) = ty else {
+        return false;
+    };
 
-            // [std|core]::option::Option
-            3 if (path.segments[0].ident == "std" || path.segments[0].ident == "core") && path.segments[1].ident == "option" && path.segments[2].ident == "Option" => true,
+    // Only the trailing segments are matched, so this keeps working however many segments lead up to them, e.g. for
+    // `use std::option;` or new prelude editions.
+    path_matches(path, |idents| {
+        matches!(
+            idents,
+            ["Option"] | [.., "option", "Option"] | ["std" | "core", "prelude", .., "Option"]
+        )
+    })
+}
 
-            // [std|core]::prelude::*::Option
-            4 if (path.segments[0].ident == "std" || path.segments[0].ident == "core") && path.segments[1].ident == "prelude" && path.segments[3].ident == "Option" => true,
+/// Passes the identifiers of a path's segments to `matches`, e.g. `["std", "option", "Option"]`, so that a type can be
+/// recognised by matching them against slice patterns.
+///
+/// Leading `::`s and turbofish generics don't show up in the identifiers, and raw identifiers such as `r#Option` are
+/// passed without their `r#`.
+fn path_matches(path: &syn::Path, matches: impl FnOnce(&[&str]) -> bool) -> bool {
+    let idents: Vec<String> = path
+        .segments
+        .iter()
+        .map(|segment| segment.ident.unraw().to_string())
+        .collect();
+    matches(&idents.iter().map(String::as_str).collect::<Vec<_>>())
+}
 
-            _ => false,
-        }
+/// Returns whether a [`syn::Type`] is a [`Vec<T>`], matching its paths like [`is_ty_option`] does.
+fn is_ty_vec(ty: &syn::Type) -> bool {
+    let syn::Type::Path(syn::TypePath { qself: None, path }) = ty else {
+        return false;
+    };
+    path_matches(path, |idents| {
+        matches!(
+            idents,
+            ["Vec"] | [.., "vec", "Vec"] | ["std" | "alloc", "prelude", .., "Vec"]
+        )
+    })
+}
+
+/// Returns whether a [`syn::Type`] is a `HashMap<K, V>` or a `BTreeMap<K, V>`, matching its paths like [`is_ty_option`]
+/// does.
+fn is_ty_map(ty: &syn::Type) -> bool {
+    let syn::Type::Path(syn::TypePath { qself: None, path }) = ty else {
+        return false;
+    };
+    path_matches(path, |idents| {
+        matches!(
+            idents,
+            ["HashMap" | "BTreeMap"]
+                | [.., "collections", "HashMap" | "BTreeMap"]
+                | [.., "hash_map", "HashMap"]
+                | [.., "btree_map", "BTreeMap"]
+        )
+    })
+}
+
+/// Returns whether a [`syn::Type`] is a boxed error trait object, e.g. `Box<dyn Error + Send + Sync>`, or an `anyhow::Error`.
+fn is_ty_error(ty: &syn::Type) -> bool {
+    let syn::Type::Path(syn::TypePath { qself: None, path }) = ty else {
+        return false;
+    };
+    let Some(segment) = path.segments.last() else {
+        return false;
+    };
+
+    if segment.ident == "Error" {
+        return path.segments.len() == 2 && path.segments[0].ident == "anyhow";
+    } else if segment.ident != "Box" {
+        return false;
+    }
+
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return false;
+    };
+    if let Some(syn::GenericArgument::Type(syn::Type::TraitObject(object))) = args.args.first() {
+        object.bounds.iter().any(|bound| {
+            matches!(bound, syn::TypeParamBound::Trait(bound) if bound.path.segments.last().is_some_and(|segment| segment.ident == "Error"))
+        })
     } else {
         false
     }
 }
 
+#[rustfmt::skip]
+/// Returns whether a [`syn::Type`] is a `String`, `str` or `Cow<str>`, which can be redacted as a `&str`.
+fn is_ty_str(ty: &syn::Type) -> bool {
+    let syn::Type::Path(syn::TypePath { qself: None, path }) = ty else {
+        return false;
+    };
+    match path.segments.len() {
+        1 if path.segments[0].ident == "String" || path.segments[0].ident == "str" => {
+            return path.segments[0].arguments.is_none();
+        }
+
+        // [std|alloc]::string::String
+        3 if (path.segments[0].ident == "std" || path.segments[0].ident == "alloc") && path.segments[1].ident == "string" && path.segments[2].ident == "String" => return true,
+
+        _ => {}
+    }
+
+    // Cow<'_, str>, [std|alloc]::borrow::Cow<'_, str>
+    let Some(segment) = path.segments.last().filter(|segment| segment.ident == "Cow") else {
+        return false;
+    };
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return false;
+    };
+    args.args.iter().any(|arg| {
+        matches!(arg, syn::GenericArgument::Type(syn::Type::Path(ty)) if ty.qself.is_none() && ty.path.is_ident("str"))
+    })
+}
+
+/// Returns whether a [`syn::Type`] is a `Wrapping<T>` or `Saturating<T>`, which are redacted as the number they wrap so
+/// that the output doesn't depend on whether their [`Debug`] output shows the wrapper.
+fn is_ty_num_wrapper(ty: &syn::Type) -> bool {
+    let syn::Type::Path(syn::TypePath { qself: None, path }) = ty else {
+        return false;
+    };
+    path_matches(path, |idents| {
+        matches!(
+            idents,
+            ["Wrapping" | "Saturating"]
+                | ["num", "Wrapping" | "Saturating"]
+                | ["std" | "core", "num", "Wrapping" | "Saturating"]
+        )
+    })
+}
+
+/// Returns the type a `Box<T>`, `Rc<T>` or `Arc<T>` points to, if the [`syn::Type`] is one of them.
+///
+/// These format exactly like the value they point to, so they get its specializations, like references do.
+fn is_ty_transparent_wrapper(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(syn::TypePath { qself: None, path }) = ty else {
+        return None;
+    };
+    if !path_matches(path, |idents| {
+        matches!(
+            idents,
+            ["Box" | "Rc" | "Arc"] | [.., "boxed", "Box"] | [.., "rc", "Rc"] | [.., "sync", "Arc"]
+        )
+    }) {
+        return None;
+    }
+
+    // `Box<T, A>` may also name an allocator, so only the first argument is the value.
+    let syn::PathArguments::AngleBracketed(args) = &path.segments.last()?.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        syn::GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
+}
+
+/// Detects the specialization to apply to a field of the given [`syn::Type`], with any references already removed.
+fn detect_specialization(ty: &syn::Type) -> Option<Specialization> {
+    if is_ty_option(ty) {
+        Some(Specialization::Option)
+    } else if is_ty_vec(ty) {
+        Some(Specialization::Vec)
+    } else if is_ty_map(ty) {
+        Some(Specialization::Map)
+    } else if is_ty_error(ty) {
+        Some(Specialization::Error)
+    } else if is_ty_str(ty) {
+        Some(Specialization::Str)
+    } else if is_ty_num_wrapper(ty) {
+        Some(Specialization::NumWrapper)
+    } else if let Some(inner) = is_ty_transparent_wrapper(ty) {
+        match detect_specialization(peel_refs(inner)) {
+            // Only `Box<str>`, `Rc<str>` and `Arc<str>` can be borrowed as a `&str` themselves, so other strings are
+            // formatted like any other value.
+            Some(Specialization::Str) if !matches!(inner, syn::Type::Path(path) if path.path.is_ident("str")) => None,
+            specialization => specialization,
+        }
+    } else {
+        None
+    }
+}
+
+#[cfg(feature = "secrecy")]
+/// Returns whether a [`syn::Type`] is one of `secrecy`'s wrapper types, whose [`Debug`] output is already redacted.
+///
+/// The type must be written with its `secrecy::` path, so that a type of the user's own with the same name is never
+/// passed through unredacted.
+fn is_ty_secret(ty: &syn::Type) -> bool {
+    let syn::Type::Path(syn::TypePath { qself: None, path }) = ty else {
+        return false;
+    };
+    path_matches(path, |idents| {
+        matches!(
+            idents,
+            [
+                "secrecy",
+                "Secret" | "SecretBox" | "SecretString" | "SecretSlice" | "SecretVec"
+            ]
+        )
+    })
+}
+
+/// Returns whether a [`syn::Type`] is a `Weak<T>` from `std::rc` or `std::sync`, whose [`Debug`] output is always `(Weak)`.
+///
+/// The type must be written with its full `std::` or `alloc::` path, as the `Debug` output of other types named `Weak`
+/// may contain data, which would then be shown unredacted.
+fn is_ty_weak(ty: &syn::Type) -> bool {
+    let syn::Type::Path(syn::TypePath { qself: None, path }) = ty else {
+        return false;
+    };
+    path_matches(path, |idents| {
+        matches!(idents, ["std" | "alloc", "rc" | "sync", "Weak"])
+    })
+}
+
+/// Returns whether a [`syn::Type`] is a function pointer or a closure trait object, e.g. `fn(u32) -> u32`,
+/// `Box<dyn Fn()>` or `Arc<dyn FnMut() + Send>`, which have no meaningful [`Debug`] output.
+fn is_ty_fn(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::BareFn(_) => true,
+        syn::Type::TraitObject(object) => object.bounds.iter().any(|bound| {
+            matches!(bound, syn::TypeParamBound::Trait(bound) if bound.path.segments.last().is_some_and(|segment| {
+                segment.ident == "Fn" || segment.ident == "FnMut" || segment.ident == "FnOnce"
+            }))
+        }),
+        syn::Type::Path(syn::TypePath { qself: None, path }) => {
+            let Some(segment) = path.segments.last() else {
+                return false;
+            };
+            if segment.ident != "Box" && segment.ident != "Rc" && segment.ident != "Arc" {
+                return false;
+            }
+            let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+                return false;
+            };
+            matches!(args.args.first(), Some(syn::GenericArgument::Type(ty)) if is_ty_fn(peel_refs(ty)))
+        }
+        _ => false,
+    }
+}
+
+#[cfg(feature = "pedantic")]
+/// Returns whether a [`syn::Type`] is a `bool` or `()`, which reveal little to nothing when printed,
+/// so redacting them is most likely a mistake.
+fn is_ty_low_value(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => path.is_ident("bool"),
+        syn::Type::Tuple(tuple) => tuple.elems.is_empty(),
+        syn::Type::Paren(syn::TypeParen { elem, .. }) | syn::Type::Group(syn::TypeGroup { elem, .. }) => {
+            is_ty_low_value(elem)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(feature = "pedantic")]
+/// Generates tokens that trigger a `deprecated` warning pointing at the given type, as proc macros
+/// can't emit warnings on stable Rust.
+fn generate_low_value_warning(ty: &syn::Type) -> proc_macro2::TokenStream {
+    quote_spanned! {ty.span()=>
+        const _: () = {
+            #[deprecated(note = "redacting a `bool` or `()` is likely a mistake as it reveals little to nothing anyway (this warning is emitted by veil's `pedantic` feature)")]
+            const REDACTED_LOW_VALUE_TYPE: () = ();
+            REDACTED_LOW_VALUE_TYPE
+        };
+    }
+}
+
+#[cfg(feature = "strict")]
+/// Generates tokens that trigger a `deprecated` warning pointing at a field's `#[redact(...)]` attribute that repeats
+/// the modifiers the field already inherits from `#[redact(all, ...)]`.
+fn generate_redundant_warning(span: proc_macro2::Span) -> proc_macro2::TokenStream {
+    quote_spanned! {span=>
+        const _: () = {
+            #[deprecated(note = "this `#[redact(...)]` is redundant, the field already inherits the same modifiers from `#[redact(all, ...)]` (this warning is emitted by veil's `strict` feature)")]
+            const REDACTED_REDUNDANT_MODIFIERS: () = ();
+            REDACTED_REDUNDANT_MODIFIERS
+        };
+    }
+}
+
+#[cfg(not(feature = "strict"))]
+fn generate_display_ignored_warning(span: proc_macro2::Span) -> proc_macro2::TokenStream {
+    quote_spanned! {span=>
+        const _: () = {
+            #[deprecated(note = "`display` has no effect with `fixed`, the data is always redacted as a fixed number of characters (this is an error with veil's `strict` feature)")]
+            const REDACTED_DISPLAY_IGNORED: () = ();
+            REDACTED_DISPLAY_IGNORED
+        };
+    }
+}
+
+/// Generates the start of a `Debug` implementation that writes `...` instead of the value where it's nested within
+/// itself more than `max_depth` times, for `#[redact(max_depth = N)]`.
+///
+/// Each type counts its own depth for each thread, so that the limit only depends on how deeply it recurses.
+pub(crate) fn generate_depth_guard(max_depth: Option<std::num::NonZeroU8>) -> proc_macro2::TokenStream {
+    let Some(max_depth) = max_depth else {
+        return proc_macro2::TokenStream::new();
+    };
+    let max_depth = usize::from(max_depth.get());
+    quote! {
+        ::std::thread_local! {
+            static DEPTH: ::std::cell::Cell<usize> = const { ::std::cell::Cell::new(0) };
+        }
+        let ::std::option::Option::Some(_depth) = veil::private::DepthGuard::enter(&DEPTH, #max_depth) else {
+            return fmt.write_str("...");
+        };
+    }
+}
+
+/// How the generated code gets a reference to the value of each field.
+#[derive(Clone, Copy)]
+pub(crate) enum FieldAccess {
+    /// Through the bindings of a destructured enum variant, e.g. `field` or `arg0`.
+    Bindings,
+
+    /// Through `self`, e.g. `&self.field`.
+    SelfRef,
+
+    /// Through a copy of each field of `self`, bound before the fields are formatted, as fields of `#[repr(packed)]`
+    /// structs can't be borrowed in place.
+    SelfCopy,
+}
+
 pub(crate) enum FormatData<'a> {
     /// Structs, struct enum variants
     FieldsNamed(&'a syn::FieldsNamed),
@@ -35,67 +354,187 @@ pub(crate) enum FormatData<'a> {
     /// Tuple structs, tuple enum variants
     FieldsUnnamed(&'a syn::FieldsUnnamed),
 }
+/// The tokens needed to format the fields of a struct or enum variant.
+pub(crate) struct FormattedFields {
+    /// Statements that must run before the fields are formatted.
+    pub(crate) prelude: Vec<proc_macro2::TokenStream>,
+
+    /// The names the fields are shown under. Empty for tuple structs and tuple enum variants.
+    pub(crate) names: Vec<String>,
+
+    /// Expressions that evaluate to a reference to something implementing [`Debug`] for each field.
+    pub(crate) bodies: Vec<proc_macro2::TokenStream>,
+}
+
 impl FormatData<'_> {
     /// `name`: The name of the struct or enum variant.
     ///
-    /// `all_field_flags`: `FieldFlags` that apply to all fields, if set
+    /// `extra_fields`: Computed fields from `#[redact(extra_field(...))]`, shown after the other fields. Requires access through `self`.
     ///
-    /// `with_self`: prepends `self.` to the field name for accessing struct fields
+    /// See [`FormatData::fields`] for the other arguments.
     pub(crate) fn impl_debug(
         self,
         name: proc_macro2::TokenStream,
+        path: &str,
         all_fields_flags: Option<FieldFlags>,
-        with_self: bool,
+        extra_fields: &[ExtraField],
+        access: FieldAccess,
         unused: &mut UnusedDiagnostic,
     ) -> Result<proc_macro2::TokenStream, syn::Error> {
+        let FormattedFields {
+            prelude,
+            names: mut field_names,
+            bodies: mut field_bodies,
+        } = self.fields(path, all_fields_flags, access, unused)?;
+
+        for ExtraField { name, method, flags } in extra_fields {
+            // The method is called right here, so a missing or private method points at `source`.
+            let accessor = quote_spanned! {method.span()=> &self.#method() };
+            field_bodies.push(generate_redact_call(accessor, name, None, flags, unused));
+            field_names.push(name.clone());
+        }
+
+        Ok(match self {
+            Self::FieldsNamed(syn::FieldsNamed { .. }) => {
+                quote! {
+                    #(#prelude)*
+                    fmt.debug_struct(#name)
+                    #(
+                        .field(#field_names, &#field_bodies)
+                    )*
+                    .finish()?
+                }
+            }
+
+            Self::FieldsUnnamed(syn::FieldsUnnamed { .. }) => {
+                quote! {
+                    #(#prelude)*
+                    fmt.debug_tuple(#name)
+                    #(
+                        .field(&#field_bodies)
+                    )*
+                    .finish()?
+                }
+            }
+        })
+    }
+
+    /// `path`: The path of the struct or enum variant (e.g. `Enum::Variant`), used to identify fields
+    ///
+    /// `all_field_flags`: `FieldFlags` that apply to all fields, if set
+    ///
+    /// `access`: how to get a reference to the value of each field
+    pub(crate) fn fields(
+        &self,
+        #[cfg_attr(not(feature = "audit"), allow(unused_variables))] path: &str,
+        all_fields_flags: Option<FieldFlags>,
+        access: FieldAccess,
+        unused: &mut UnusedDiagnostic,
+    ) -> Result<FormattedFields, syn::Error> {
         let fields = match self {
             Self::FieldsNamed(syn::FieldsNamed { named: fields, .. })
             | Self::FieldsUnnamed(syn::FieldsUnnamed { unnamed: fields, .. }) => fields,
         };
 
+        // Under the `strict` feature, we keep track of how many fields opted out of `#[redact(all)]`.
+        #[cfg(feature = "strict")]
+        let mut skipped_fields = 0;
+
+        // Statements that run before the fields are formatted. Under the `pedantic` feature, these emit warnings
+        // for fields that are likely redacted by mistake, and under the `audit` feature, they record redacted fields.
+        #[allow(unused_mut)]
+        let mut prelude: Vec<proc_macro2::TokenStream> = Vec::new();
+
+        let mut field_names = Vec::with_capacity(fields.len());
         let mut field_bodies = Vec::with_capacity(fields.len());
+        let mut field_orders = Vec::with_capacity(fields.len());
         for (i, field) in fields.iter().enumerate() {
             // The field accessor is how we actually get a reference to the value of a field.
             // This could be `self.field`, `self.0`, or just `field` or `arg0`, depending on whether
             // we destructured the enum variant or we're printing a struct.
-            #[allow(clippy::collapsible_else_if)]
-            let field_accessor = if with_self {
-                if let Some(ident) = &field.ident {
-                    quote! { &self.#ident }
-                } else {
-                    let i = syn::Index::from(i);
-                    quote! { &self.#i }
-                }
-            } else {
-                if let Some(ident) = &field.ident {
-                    ident.into_token_stream()
-                } else {
-                    syn::Ident::new(&format!("arg{i}"), field.span()).into_token_stream()
+            let member = match &field.ident {
+                Some(ident) => syn::Member::Named(ident.clone()),
+                None => syn::Member::Unnamed(syn::Index::from(i)),
+            };
+            let field_accessor = match access {
+                FieldAccess::Bindings => match &field.ident {
+                    Some(ident) => ident.into_token_stream(),
+                    None => syn::Ident::new(&format!("arg{i}"), field.span()).into_token_stream(),
+                },
+                FieldAccess::SelfRef => quote! { &self.#member },
+                FieldAccess::SelfCopy => {
+                    // Copied into its own binding rather than borrowed as a temporary, which wouldn't live long
+                    // enough when the field is bound to a name itself, e.g. with `#[redact(cfg(...))]`.
+                    let copy = quote::format_ident!("__veil_copy_{}", i);
+                    prelude.push(quote! { let #copy = self.#member; });
+                    quote! { &#copy }
                 }
             };
 
             // Parse field flags from attributes on this field
             let field_flags = match field.attrs.len() {
-                0 => all_fields_flags,
+                // Fields of types trusted to redact themselves are shown as-is, as if they were skipped.
+                0 if all_fields_flags.as_ref().is_some_and(|flags| {
+                    flags
+                        .trust_inner
+                        .contains(&peel_refs(&field.ty).to_token_stream().to_string())
+                }) =>
+                {
+                    Some(FieldFlags {
+                        skip: true,
+                        ..Default::default()
+                    })
+                }
+                0 => all_fields_flags.clone(),
                 1 => match FieldFlags::extract::<1>(
                     "Redact",
                     &field.attrs,
                     FieldFlagsParse {
                         skip_allowed: all_fields_flags.is_some(),
+                        transparent_allowed: false,
+                        no_display_allowed: all_fields_flags.as_ref().is_some_and(|flags| flags.display),
                     },
                 )? {
                     [Some(flags)] => {
                         if flags.variant {
                             return Err(syn::Error::new(
                                 field.attrs[0].span(),
-                                "`#[redact(variant)]` is invalid for structs",
+                                "`variant` only applies to enums and enum variants, not fields",
                             ));
                         } else if flags.all {
                             return Err(syn::Error::new(
                                 field.attrs[0].span(),
                                 "`#[redact(all)]` is invalid for struct fields",
                             ));
+                        } else if !flags.extra_fields.is_empty() {
+                            return Err(syn::Error::new(
+                                field.attrs[0].span(),
+                                "`extra_field` is only valid on structs with named fields",
+                            ));
+                        } else if flags.max_depth.is_some() {
+                            return Err(syn::Error::new(
+                                field.attrs[0].span(),
+                                "`max_depth` is only valid on structs and enums, not fields",
+                            ));
                         } else {
+                            #[cfg(feature = "strict")]
+                            if let Some(all_fields_flags) = &all_fields_flags {
+                                if flags
+                                    == (FieldFlags {
+                                        all: false,
+                                        trust_inner: Vec::new(),
+                                        ..all_fields_flags.clone()
+                                    })
+                                {
+                                    prelude.push(generate_redundant_warning(field.attrs[0].span()));
+                                }
+                            }
+
+                            #[cfg(feature = "pedantic")]
+                            if !flags.skip && is_ty_low_value(&field.ty) {
+                                prelude.push(generate_low_value_warning(&field.ty));
+                            }
+
                             Some(flags)
                         }
                     }
@@ -110,50 +549,234 @@ impl FormatData<'_> {
                 }
             };
 
-            // If we have field flags...
-            if let Some(field_flags) = field_flags {
-                // Redact it!
+            #[cfg(not(feature = "strict"))]
+            if field_flags.as_ref().is_some_and(FieldFlags::is_display_ignored) {
+                prelude.push(generate_display_ignored_warning(
+                    field.attrs.first().map_or_else(|| field.span(), Spanned::span),
+                ));
+            }
 
-                // Specialization for Option<T>
-                let is_option = is_ty_option(&field.ty);
+            #[cfg(feature = "strict")]
+            if let Some(FieldFlags { skip: true, .. }) = field_flags {
+                skipped_fields += 1;
+            }
 
-                field_bodies.push(generate_redact_call(field_accessor, is_option, &field_flags, unused));
-            } else {
-                // Otherwise, just use the normal `Debug` implementation.
-                field_bodies.push(quote! { #field_accessor });
+            // The name the field is shown under, which may have been overridden with `#[redact(rename = "...")]`
+            let rename = field_flags.as_ref().and_then(|flags| flags.rename.clone());
+            if let Some(ident) = &field.ident {
+                field_names.push(rename.unwrap_or_else(|| ident.to_string()));
+            } else if rename.is_some() {
+                return Err(syn::Error::new(
+                    field.attrs[0].span(),
+                    "`rename` is only valid on named struct fields",
+                ));
             }
-        }
 
-        Ok(match self {
-            Self::FieldsNamed(syn::FieldsNamed { named, .. }) => {
-                let field_names = named.iter().map(|field| field.ident.as_ref().unwrap().to_string());
+            field_orders.push(field_flags.as_ref().and_then(|flags| flags.order).unwrap_or(0));
 
-                quote! {
-                    fmt.debug_struct(#name)
-                    #(
-                        .field(#field_names, &#field_bodies)
-                    )*
-                    .finish()?
+            // Under `#[redact(cfg(...))]`, the field is only redacted when the predicate is active.
+            let cfg = field_flags.as_ref().and_then(|flags| flags.cfg.clone());
+
+            let field_ty = peel_refs(&field.ty);
+            let field_body = 'body: {
+                // Types from `secrecy` are already redacted, so unless forced we pass them through to avoid double-masking.
+                #[cfg(feature = "secrecy")]
+                if let Some(FieldFlags {
+                    skip: false,
+                    force: false,
+                    ..
+                }) = field_flags
+                {
+                    if is_ty_secret(field_ty) {
+                        unused.redacted_something();
+                        break 'body quote! { #field_accessor };
+                    }
                 }
-            }
 
-            Self::FieldsUnnamed(syn::FieldsUnnamed { .. }) => {
-                quote! {
-                    fmt.debug_tuple(#name)
-                    #(
-                        .field(&#field_bodies)
-                    )*
-                    .finish()?
+                // `Weak<T>` never shows any of its data, so masking `(Weak)` would only hide that it's a `Weak`.
+                if let Some(FieldFlags {
+                    skip: false,
+                    type_only: false,
+                    ..
+                }) = field_flags
+                {
+                    if is_ty_weak(field_ty) {
+                        unused.redacted_something();
+                        break 'body quote! { #field_accessor };
+                    }
+
+                    // Functions and closures have nothing to redact and often don't implement `Debug` at all.
+                    if is_ty_fn(field_ty) {
+                        unused.redacted_something();
+                        break 'body quote! { &veil::private::FnMarker(#field_accessor) };
+                    }
+                }
+
+                // If we have field flags...
+                if let Some(field_flags) = field_flags {
+                    // Redact it!
+
+                    #[cfg(feature = "audit")]
+                    if !field_flags.skip {
+                        let field_path = match &field.ident {
+                            Some(ident) => format!("{path}::{ident}"),
+                            None => format!("{path}::{i}"),
+                        };
+                        let ty = &field.ty;
+                        let cfg = cfg.iter();
+                        prelude.push(quote! {
+                            #(#[cfg(#cfg)])*
+                            veil::audit::record(#field_path, ::core::any::type_name::<#ty>());
+                        });
+                    }
+
+                    // Under the `redact-noop` feature, the field is passed through by `generate_redact_call` instead.
+                    if field_flags.type_only && !cfg!(feature = "redact-noop") {
+                        // Only show the type of the field, which is never sensitive.
+                        unused.redacted_something();
+                        let field_type = type_label(&field.ty);
+                        break 'body quote! {
+                            &veil::private::TypeOnlyFormatter { this: #field_accessor, ty: #field_type }
+                        };
+                    }
+
+                    if field_flags.redactable {
+                        // Use the type's own `Redactable` implementation. Naming the type here makes a missing
+                        // implementation point at the field's type.
+                        unused.redacted_something();
+                        let ty = &field.ty;
+                        break 'body quote_spanned! {ty.span()=> &veil::private::RedactableFormatter::<#ty>(#field_accessor) };
+                    }
+
+                    // The name the field is shown under, or its index for tuple fields
+                    let field_name = match &field.ident {
+                        Some(_) => field_names.last().cloned().unwrap_or_default(),
+                        None => i.to_string(),
+                    };
+
+                    if let Some(method) = &field_flags.try_display {
+                        // The method is called right here, so a missing method or one that doesn't return a `Result`
+                        // points at `try_display`.
+                        let accessor = quote_spanned! {method.span()=>
+                            &veil::private::TryDisplay((#field_accessor).#method())
+                        };
+                        let field_flags = FieldFlags {
+                            display: true,
+                            ..field_flags
+                        };
+                        break 'body generate_redact_call(accessor, &field_name, None, &field_flags, unused);
+                    }
+
+                    // Specializations for Option<T>, Vec<T>, errors and strings, also behind a `Box<T>`, `Rc<T>` or
+                    // `Arc<T>`, unless one was requested with `#[redact(as = "...")]`
+                    let specialization = field_flags.specialization.or_else(|| detect_specialization(field_ty));
+
+                    if field_flags.mask_none && specialization != Some(Specialization::Option) {
+                        return Err(syn::Error::new(
+                            field.attrs.first().map_or_else(|| field.span(), Spanned::span),
+                            "`mask_none` is only valid on `Option<T>` fields",
+                        ));
+                    } else if field_flags.values && specialization != Some(Specialization::Map) {
+                        return Err(syn::Error::new(
+                            field.attrs.first().map_or_else(|| field.span(), Spanned::span),
+                            "`values` is only valid on `HashMap<K, V>` and `BTreeMap<K, V>` fields, or on type aliases of them with `as` set to `Map`",
+                        ));
+                    }
+
+                    generate_redact_call(
+                        field_accessor.clone(),
+                        &field_name,
+                        specialization,
+                        &field_flags,
+                        unused,
+                    )
+                } else {
+                    // Otherwise, just use the normal `Debug` implementation.
+                    quote! { #field_accessor }
                 }
+            };
+
+            if let Some(cfg) = cfg {
+                // Bind the redacted and the plain field to the same name, and let the compiler pick one.
+                let binding = quote::format_ident!("__veil_field_{}", i);
+                prelude.push(quote! {
+                    #[cfg(#cfg)]
+                    let #binding = #field_body;
+                    #[cfg(not(#cfg))]
+                    let #binding = #field_accessor;
+                });
+                field_bodies.push(binding.into_token_stream());
+            } else {
+                field_bodies.push(field_body);
             }
+        }
+
+        // An enum variant opted out of its enum's `#[redact(all)]` with `#[redact(all, skip)]` skips every field on purpose.
+        #[cfg(feature = "strict")]
+        if all_fields_flags.as_ref().is_some_and(|flags| !flags.skip)
+            && !fields.is_empty()
+            && skipped_fields == fields.len()
+        {
+            return Err(syn::Error::new(
+                fields.span(),
+                "`#[redact(all)]` has no effect because every field is marked as `#[redact(skip)]`",
+            ));
+        }
+
+        // Show the fields in ascending `#[redact(order = N)]`. The sort is stable, so ties keep their declaration order.
+        if field_orders.iter().any(|&order| order != 0) {
+            let mut indices = (0..field_bodies.len()).collect::<Vec<_>>();
+            indices.sort_by_key(|&i| field_orders[i]);
+
+            if !field_names.is_empty() {
+                field_names = indices.iter().map(|&i| field_names[i].clone()).collect();
+            }
+            field_bodies = indices.iter().map(|&i| field_bodies[i].clone()).collect();
+        }
+
+        Ok(FormattedFields {
+            prelude,
+            names: field_names,
+            bodies: field_bodies,
         })
     }
 }
 
+/// Formats a type like it would be written in source code, e.g. `Option<Vec<u8>>`.
+fn type_label(ty: &syn::Type) -> String {
+    let mut label = ty.to_token_stream().to_string();
+
+    // `TokenStream`'s `Display` implementation puts spaces between most tokens.
+    for (spaced, unspaced) in [
+        (" :: ", "::"),
+        (":: ", "::"),
+        (" < ", "<"),
+        ("< ", "<"),
+        (" <", "<"),
+        (" >", ">"),
+        (" ,", ","),
+        ("& ", "&"),
+        ("( ", "("),
+        (" )", ")"),
+        ("[ ", "["),
+        (" ]", "]"),
+        (" ;", ";"),
+    ] {
+        label = label.replace(spaced, unspaced);
+    }
+
+    label
+}
+
 /// Generates a call to `veil::private::redact`
+///
+/// `field_name`: The name of the field (or enum variant) being redacted, passed to `#[redact(with_fn_ctx = ...)]`
+///
+/// `specialization`: The specialization to apply, if any
 pub(crate) fn generate_redact_call(
     field_accessor: proc_macro2::TokenStream,
-    is_option: bool,
+    field_name: &str,
+    specialization: Option<Specialization>,
     field_flags: &FieldFlags,
     unused: &mut UnusedDiagnostic,
 ) -> proc_macro2::TokenStream {
@@ -161,32 +784,244 @@ pub(crate) fn generate_redact_call(
         // This is the one place where we actually track whether the derive macro had any effect! Nice.
         unused.redacted_something();
 
-        let specialization = if is_option {
-            quote! { ::std::option::Option::Some(veil::private::RedactSpecialization::Option) }
+        let target = if specialization == Some(Specialization::Str) {
+            // String slices are redacted without formatting them first.
+            let debug = !field_flags.display;
+            quote! {
+                veil::private::RedactionTarget::Str {
+                    this: ::std::convert::AsRef::<str>::as_ref(#field_accessor),
+                    debug: #debug
+                }
+            }
+        } else if specialization == Some(Specialization::NumWrapper) {
+            // The wrapped number is formatted itself, in case the wrapper's own output shows its name.
+            if field_flags.display {
+                quote! { veil::private::RedactionTarget::Display(&(#field_accessor).0) }
+            } else {
+                quote! { veil::private::RedactionTarget::Debug { this: &(#field_accessor).0, alternate } }
+            }
+        } else if field_flags.display {
+            // std::fmt::Display
+            quote! { veil::private::RedactionTarget::Display(#field_accessor) }
         } else {
-            quote! { ::std::option::Option::None }
+            // std::fmt::Debug
+            quote! { veil::private::RedactionTarget::Debug { this: #field_accessor, alternate } }
         };
 
-        if field_flags.display {
-            // std::fmt::Display
+        let specialization = match specialization {
+            Some(Specialization::Option) => {
+                quote! { ::std::option::Option::Some(veil::private::RedactSpecialization::Option) }
+            }
+            Some(Specialization::Error) => {
+                quote! { ::std::option::Option::Some(veil::private::RedactSpecialization::Error) }
+            }
+            Some(Specialization::Vec) => {
+                quote! { ::std::option::Option::Some(veil::private::RedactSpecialization::Vec) }
+            }
+            Some(Specialization::Map) if field_flags.values => {
+                quote! { ::std::option::Option::Some(veil::private::RedactSpecialization::Map) }
+            }
+            Some(Specialization::Map | Specialization::Str | Specialization::NumWrapper) | None => {
+                quote! { ::std::option::Option::None }
+            }
+        };
+
+        if cfg!(feature = "redact-noop") {
+            // The field is still counted as redacted, so the annotations compile exactly as they would otherwise.
+            return quote! { &veil::private::PassthroughFormatter(#target) };
+        }
+
+        let redact = if let Some(FlagPath(with_fn)) = &field_flags.with_fn_ctx {
+            // #[redact(with_fn_ctx = ...)]
+            let redact_fn = quote_spanned! {with_fn.span()=> #with_fn };
             quote! {
-                &veil::private::RedactionFormatter {
-                    this: veil::private::RedactionTarget::Display(#field_accessor),
-                    flags: veil::private::RedactFlags { #field_flags },
-                    specialization: #specialization
+                &veil::private::RedactionFnFormatter {
+                    this: #target,
+                    field: #field_name,
+                    flags: #field_flags,
+                    redact: #redact_fn
                 }
             }
         } else {
-            // std::fmt::Debug
             quote! {
                 &veil::private::RedactionFormatter {
-                    this: veil::private::RedactionTarget::Debug { this: #field_accessor, alternate },
-                    flags: veil::private::RedactFlags { #field_flags },
+                    this: #target,
+                    flags: #field_flags,
                     specialization: #specialization
                 }
             }
+        };
+
+        if field_flags.catch {
+            // #[redact(catch)]
+            // Both formatters format the data into a buffer before writing anything, so a panic in the field's own
+            // formatting never leaves partial output behind. Only when redaction is disabled with the `toggle`
+            // feature is the data written as-is, along with whatever it wrote before panicking.
+            quote! { &veil::private::CatchUnwind(#redact) }
+        } else {
+            redact
         }
     } else {
         field_accessor
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_option(ty: &str) -> bool {
+        is_ty_option(peel_refs(&syn::parse_str(ty).unwrap()))
+    }
+
+    #[test]
+    fn detects_option() {
+        for ty in [
+            "Option<String>",
+            "Option::<String>",
+            "r#Option<String>",
+            "option::Option<String>",
+            "std::option::Option<String>",
+            "::core::option::Option<T>",
+            "::std::option::Option::<T>",
+            "core::option::r#Option<T>",
+            "std::prelude::rust_2021::Option<T>",
+            "::core::prelude::v1::Option<T>",
+            "std::prelude::rust_2024::Option<T>",
+            "::core::prelude::rust_2015::r#Option<T>",
+            "std::prelude::v1::rust_2021::Option<T>",
+            "my_crate::option::Option<String>",
+            "::my_crate::reexports::std::option::Option<String>",
+            "(Option<String>)",
+            "&'a mut Option<String>",
+        ] {
+            assert!(is_option(ty), "{ty} should be detected as an `Option`");
+        }
+
+        // Types passed to `macro_rules!` macros as `$ty:ty` are wrapped in an invisible group.
+        let group = syn::Type::Group(syn::TypeGroup {
+            group_token: Default::default(),
+            elem: Box::new(syn::parse_str("::std::option::Option<u8>").unwrap()),
+        });
+        assert!(is_ty_option(peel_refs(&group)));
+    }
+
+    #[test]
+    fn detects_vec() {
+        let is_vec = |ty: &str| is_ty_vec(peel_refs(&syn::parse_str(ty).unwrap()));
+        for ty in [
+            "Vec<String>",
+            "Vec::<String>",
+            "r#Vec<String>",
+            "vec::Vec<String>",
+            "std::vec::Vec<String>",
+            "::alloc::vec::Vec<T>",
+            "std::prelude::rust_2021::Vec<T>",
+            "&Vec<Option<String>>",
+        ] {
+            assert!(is_vec(ty), "{ty} should be detected as a `Vec`");
+        }
+        for ty in [
+            "VecDeque<String>",
+            "my_crate::Vec<String>",
+            "[String]",
+            "Box<Vec<String>>",
+        ] {
+            assert!(!is_vec(ty), "{ty} shouldn't be detected as a `Vec`");
+        }
+    }
+
+    #[test]
+    fn detects_map() {
+        let is_map = |ty: &str| is_ty_map(peel_refs(&syn::parse_str(ty).unwrap()));
+        for ty in [
+            "HashMap<String, String>",
+            "BTreeMap<&'a str, u8>",
+            "std::collections::HashMap<String, String>",
+            "::std::collections::BTreeMap<String, String>",
+            "hash_map::HashMap<K, V, S>",
+            "alloc::collections::btree_map::BTreeMap<K, V>",
+            "&HashMap<String, String>",
+        ] {
+            assert!(is_map(ty), "{ty} should be detected as a map");
+        }
+        for ty in [
+            "HashSet<String>",
+            "my_crate::HashMap<String, String>",
+            "IndexMap<String, String>",
+        ] {
+            assert!(!is_map(ty), "{ty} shouldn't be detected as a map");
+        }
+    }
+
+    #[test]
+    fn detects_transparent_wrappers() {
+        let specialization = |ty: &str| detect_specialization(peel_refs(&syn::parse_str(ty).unwrap()));
+        for ty in [
+            "Box<Option<String>>",
+            "std::boxed::Box<Option<String>>",
+            "::alloc::boxed::Box<&'a Option<String>>",
+            "Rc<Option<String>>",
+            "alloc::rc::Rc<Option<String>>",
+            "Arc<Option<String>>",
+            "std::sync::Arc<Option<String>>",
+            "::std::sync::Arc<Box<Option<String>>>",
+            "&Arc<std::option::Option<u8>>",
+        ] {
+            assert!(
+                specialization(ty) == Some(Specialization::Option),
+                "{ty} should get the `Option` specialization"
+            );
+        }
+
+        assert!(specialization("Arc<str>") == Some(Specialization::Str));
+        assert!(specialization("Box<Vec<u8>>") == Some(Specialization::Vec));
+        assert!(specialization("Box<dyn std::error::Error>") == Some(Specialization::Error));
+        assert!(specialization("Rc<Wrapping<u64>>") == Some(Specialization::NumWrapper));
+        for ty in [
+            "Box<String>",
+            "Arc<&'static str>",
+            "Arc<u64>",
+            "my_crate::Arc<Option<u8>>",
+            "Cell<Option<u8>>",
+        ] {
+            assert!(specialization(ty).is_none(), "{ty} shouldn't get a specialization");
+        }
+    }
+
+    #[test]
+    fn detects_num_wrappers() {
+        let is_num_wrapper = |ty: &str| is_ty_num_wrapper(peel_refs(&syn::parse_str(ty).unwrap()));
+        for ty in [
+            "Wrapping<u64>",
+            "Saturating<i32>",
+            "num::Wrapping<u8>",
+            "::std::num::Wrapping<u64>",
+            "core::num::Saturating<u16>",
+            "&Wrapping<u64>",
+        ] {
+            assert!(is_num_wrapper(ty), "{ty} should be detected as a numeric wrapper");
+        }
+        for ty in ["u64", "Wrapper<u64>", "my_crate::Wrapping<u64>", "std::num::NonZeroU64"] {
+            assert!(!is_num_wrapper(ty), "{ty} shouldn't be detected as a numeric wrapper");
+        }
+    }
+
+    #[test]
+    fn ignores_other_types() {
+        for ty in [
+            "String",
+            "MyOption<String>",
+            "option::MyOption<String>",
+            "Vec<Option<String>>",
+            "my_crate::Option<String>",
+            "my_crate::prelude::Option<String>",
+            "std::prelude::rust_2021::MyOption<String>",
+            "std::option::Option<String>::Item",
+            "<Option<String> as IntoIterator>::Item",
+            "[Option<String>; 1]",
+        ] {
+            assert!(!is_option(ty), "{ty} shouldn't be detected as an `Option`");
+        }
+    }
+}
@@ -9,6 +9,7 @@ mod enums;
 mod flags;
 mod fmt;
 mod redact;
+mod redact_impl;
 mod redactable;
 mod sanitize;
 mod structs;
@@ -33,6 +34,17 @@ pub fn derive_redactable(item: TokenStream) -> TokenStream {
     redactable::derive(item)
 }
 
+#[proc_macro_attribute]
+/// Redacts fields in a manual [`Debug`] implementation.
+///
+/// Place this on an `impl` block or a function and use `veil_field!(builder, "name", &value, ...)` in place of
+/// `builder.field("name", &value)` for any field that should be redacted.
+///
+/// See the [crate level documentation](index.html) for flags and modifiers.
+pub fn redact_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+    redact_impl::expand(attr, item)
+}
+
 #[doc(hidden)]
 #[proc_macro]
 /// Used by the `versioning::test_macros_version` test.
@@ -1,4 +1,8 @@
-use crate::{enums, sanitize::DeriveAttributeFilter, structs};
+use crate::{
+    enums,
+    sanitize::{is_repr_packed, DeriveAttributeFilter},
+    structs,
+};
 use proc_macro::TokenStream;
 use syn::spanned::Spanned;
 
@@ -31,6 +35,9 @@ impl Default for UnusedDiagnostic {
 }
 
 fn try_derive(mut item: syn::DeriveInput) -> Result<TokenStream, syn::Error> {
+    // `#[repr(packed)]` changes how fields can be accessed, so it has to be read before it's removed below.
+    let packed = is_repr_packed(&item.attrs);
+
     // Remove all non-veil attributes to avoid conflicting with other
     // derive proc macro attributes.
     item.retain_veil_attrs();
@@ -43,7 +50,7 @@ fn try_derive(mut item: syn::DeriveInput) -> Result<TokenStream, syn::Error> {
     let mut unused = UnusedDiagnostic::default();
 
     let tokens = match item.data {
-        syn::Data::Struct(s) => structs::derive_redact(s, item.generics, item.attrs, item.ident, &mut unused)?,
+        syn::Data::Struct(s) => structs::derive_redact(s, item.generics, item.attrs, item.ident, packed, &mut unused)?,
         syn::Data::Enum(e) => enums::derive_redact(e, item.generics, item.attrs, item.ident, &mut unused)?,
         syn::Data::Union(_) => return Err(syn::Error::new(item_span, "this trait cannot be derived for unions")),
     };
@@ -0,0 +1,216 @@
+use crate::flags::{ExtractFlags, FieldFlags, FieldFlagsParse};
+use proc_macro::TokenStream;
+use quote::ToTokens;
+use syn::{parse::Parser, spanned::Spanned, visit_mut::VisitMut};
+
+/// The arguments of a `veil_field!(builder, "name", &value, ...)` invocation.
+struct VeilField {
+    builder: syn::Expr,
+    name: syn::Expr,
+    value: syn::Expr,
+    flags: FieldFlags,
+}
+impl syn::parse::Parse for VeilField {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let builder = input.parse()?;
+        input.parse::<syn::Token![,]>()?;
+        let name = input.parse()?;
+        input.parse::<syn::Token![,]>()?;
+        let value = input.parse()?;
+
+        let mut flags = FieldFlags::default();
+        if input.parse::<Option<syn::Token![,]>>()?.is_some() {
+            let modifiers = input.fork();
+            syn::meta::parser(|mut meta| flags.parse_meta("veil_field", &mut meta))
+                .parse2(input.parse::<proc_macro2::TokenStream>()?)?;
+
+            if flags.all
+                || flags.variant
+                || flags.skip
+                || flags.rename.is_some()
+                || flags.cfg.is_some()
+                || flags.order.is_some()
+                || !flags.extra_fields.is_empty()
+                || flags.max_depth.is_some()
+            {
+                return Err(syn::Error::new(
+                    modifiers.span(),
+                    "`all`, `variant`, `skip`, `rename`, `cfg`, `order`, `extra_field` and `max_depth` are not allowed in `veil_field!`",
+                ));
+            } else if flags.with_fn_ctx.is_some() {
+                return Err(syn::Error::new(
+                    modifiers.span(),
+                    "`with_fn_ctx` is not supported in `veil_field!`, call the function directly instead",
+                ));
+            } else if flags.try_display.is_some() {
+                return Err(syn::Error::new(
+                    modifiers.span(),
+                    "`try_display` is not supported in `veil_field!`, call the method directly instead",
+                ));
+            } else if flags.type_only {
+                return Err(syn::Error::new(
+                    modifiers.span(),
+                    "`type_only` is not supported in `veil_field!` as the type of the value is unknown",
+                ));
+            } else if flags.specialization.is_some() || flags.values || flags.redactable || flags.mask_none {
+                return Err(syn::Error::new(
+                    modifiers.span(),
+                    "`as`, `values`, `redactable` and `mask_none` are not supported in `veil_field!`, redact the value with `RedactExt` or `Redactable` instead",
+                ));
+            }
+
+            // The rest is validated like a field of a derived struct, with the modifiers standing in for the attribute.
+            let attr: syn::Attribute = syn::parse_quote_spanned!(modifiers.span()=> #[redact]);
+            flags.validate(
+                &attr,
+                &FieldFlagsParse {
+                    skip_allowed: false,
+                    transparent_allowed: false,
+                    no_display_allowed: false,
+                },
+            )?;
+        }
+
+        Ok(Self {
+            builder,
+            name,
+            value,
+            flags,
+        })
+    }
+}
+impl VeilField {
+    fn into_expr(self) -> syn::Expr {
+        let Self {
+            builder,
+            name,
+            value,
+            flags,
+        } = self;
+
+        if cfg!(feature = "redact-noop") {
+            let target = if flags.display {
+                quote! { veil::private::RedactionTarget::Display(#value) }
+            } else {
+                // `alternate` is ignored when passing through, as the formatter is used directly.
+                quote! { veil::private::RedactionTarget::Debug { this: #value, alternate: false } }
+            };
+            return syn::parse_quote! {
+                #builder.field(#name, &veil::private::PassthroughFormatter(#target))
+            };
+        }
+
+        let mut formatter = if flags.display {
+            quote! {
+                veil::private::RedactionFormatter {
+                    this: veil::private::RedactionTarget::Display(#value),
+                    flags: #flags,
+                    specialization: ::std::option::Option::None
+                }
+            }
+        } else {
+            // The formatter is usually mutably borrowed by `builder` here, so we can't read whether
+            // it's in alternate mode until the field is actually formatted.
+            quote! {
+                veil::private::RedactionDebugFormatter {
+                    this: #value,
+                    flags: #flags,
+                    specialization: ::std::option::Option::None
+                }
+            }
+        };
+
+        if flags.catch {
+            formatter = quote! { veil::private::CatchUnwind(&#formatter) };
+        }
+
+        syn::parse_quote! {
+            #builder.field(#name, &#formatter)
+        }
+    }
+}
+
+/// Replaces every `veil_field!(...)` invocation with a redacted `.field(...)` call, collecting any errors.
+#[derive(Default)]
+struct ReplaceVeilFields {
+    errors: Option<syn::Error>,
+}
+impl ReplaceVeilFields {
+    fn replace(&mut self, mac: &syn::Macro) -> Option<syn::Expr> {
+        if !mac.path.is_ident("veil_field") {
+            return None;
+        }
+
+        match mac.parse_body::<VeilField>() {
+            Ok(field) => Some(field.into_expr()),
+            Err(err) => {
+                match &mut self.errors {
+                    Some(errors) => errors.combine(err),
+                    None => self.errors = Some(err),
+                }
+                None
+            }
+        }
+    }
+}
+impl VisitMut for ReplaceVeilFields {
+    fn visit_expr_mut(&mut self, expr: &mut syn::Expr) {
+        if let syn::Expr::Macro(syn::ExprMacro { mac, .. }) = expr {
+            if let Some(replacement) = self.replace(mac) {
+                *expr = replacement;
+                return;
+            }
+        }
+        syn::visit_mut::visit_expr_mut(self, expr);
+    }
+
+    fn visit_stmt_mut(&mut self, stmt: &mut syn::Stmt) {
+        if let syn::Stmt::Macro(syn::StmtMacro { mac, semi_token, .. }) = stmt {
+            if let Some(replacement) = self.replace(mac) {
+                *stmt = syn::Stmt::Expr(replacement, *semi_token);
+                return;
+            }
+        }
+        syn::visit_mut::visit_stmt_mut(self, stmt);
+    }
+}
+
+fn try_expand(attr: TokenStream, item: TokenStream) -> Result<TokenStream, syn::Error> {
+    if !attr.is_empty() {
+        return Err(syn::Error::new(
+            proc_macro2::TokenStream::from(attr).span(),
+            "`#[redact_impl]` does not take any arguments",
+        ));
+    }
+
+    let mut replace = ReplaceVeilFields::default();
+
+    let tokens = match syn::parse::<syn::Item>(item)? {
+        syn::Item::Impl(mut item) => {
+            replace.visit_item_impl_mut(&mut item);
+            item.into_token_stream()
+        }
+        syn::Item::Fn(mut item) => {
+            replace.visit_item_fn_mut(&mut item);
+            item.into_token_stream()
+        }
+        item => {
+            return Err(syn::Error::new(
+                item.span(),
+                "`#[redact_impl]` can only be used on `impl` blocks and functions",
+            ))
+        }
+    };
+
+    match replace.errors {
+        Some(errors) => Err(errors),
+        None => Ok(tokens.into()),
+    }
+}
+
+pub fn expand(attr: TokenStream, item: TokenStream) -> TokenStream {
+    match try_expand(attr, item) {
+        Ok(tokens) => tokens,
+        Err(err) => err.into_compile_error().into(),
+    }
+}
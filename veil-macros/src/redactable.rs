@@ -43,10 +43,34 @@ fn try_derive(mut item: syn::DeriveInput) -> Result<TokenStream, syn::Error> {
         ));
     }
 
-    let flags = RedactFlags::extract::<1>("Redactable", &item.attrs, ())?[0].unwrap_or_default();
+    let [flags] = RedactFlags::extract::<1>("Redactable", &item.attrs, ())?;
+    let flags = flags.unwrap_or_default();
+
+    // Generic types are usually only `Display` for some of their parameters, e.g. `impl<T: Display> Display for Wrapper<T>`,
+    // so the impl is bounded on whatever makes the type `Display`.
+    if item.generics.type_params().next().is_some() {
+        item.generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote!(Self: ::std::fmt::Display));
+    }
 
     let name_ident = &item.ident;
     let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+    if cfg!(feature = "redact-noop") {
+        return Ok(quote! {
+            impl #impl_generics veil::Redactable for #name_ident #ty_generics #where_clause {
+                fn redact(&self) -> String {
+                    ::std::string::ToString::to_string(self)
+                }
+
+                fn redact_into(&self, buffer: &mut dyn ::std::fmt::Write) -> ::std::fmt::Result {
+                    ::std::write!(buffer, "{}", self)
+                }
+            }
+        }
+        .into());
+    }
     Ok(quote! {
         impl #impl_generics veil::Redactable for #name_ident #ty_generics #where_clause {
             fn redact(&self) -> String {
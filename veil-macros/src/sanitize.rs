@@ -41,6 +41,9 @@ impl AttributeFilter for syn::Data {
 
 pub(crate) trait DeriveAttributeFilter {
     /// Removes any non-veil attributes from the derive macro input.
+    ///
+    /// This doesn't affect `#[cfg(...)]` on fields or variants, as the compiler evaluates those before expanding derive macros.
+    /// Inactive fields and variants are never seen by the macro, and active ones no longer carry their `#[cfg(...)]`.
     fn retain_veil_attrs(&mut self);
 }
 impl DeriveAttributeFilter for syn::DeriveInput {
@@ -49,3 +52,22 @@ impl DeriveAttributeFilter for syn::DeriveInput {
         self.data.retain_veil_attrs();
     }
 }
+
+/// Returns whether the attributes include `#[repr(packed)]` or `#[repr(packed(N))]`, possibly alongside other reprs.
+pub(crate) fn is_repr_packed(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().filter(|attr| attr.path().is_ident("repr")).any(|attr| {
+        let mut packed = false;
+        // Malformed reprs are reported by the compiler itself.
+        let _ = attr.parse_nested_meta(|meta| {
+            packed |= meta.path.is_ident("packed");
+            // Skip the arguments of e.g. `packed(2)` or `align(8)`.
+            if meta.input.peek(syn::token::Paren) {
+                let arguments;
+                syn::parenthesized!(arguments in meta.input);
+                arguments.parse::<proc_macro2::TokenStream>()?;
+            }
+            Ok(())
+        });
+        packed
+    })
+}
@@ -1,10 +1,11 @@
 use crate::{
-    flags::{ExtractFlags, FieldFlags, FieldFlagsParse},
-    fmt::FormatData,
+    flags::{ExtractFlags, FieldFlags, FieldFlagsParse, RedactFlags, RedactionLength},
+    fmt::{generate_depth_guard, generate_redact_call, FieldAccess, FormatData, FormattedFields},
     redact::UnusedDiagnostic,
 };
 use proc_macro::TokenStream;
 use quote::ToTokens;
+use std::num::NonZeroU8;
 use syn::spanned::Spanned;
 
 pub(super) fn derive_redact(
@@ -12,19 +13,106 @@ pub(super) fn derive_redact(
     generics: syn::Generics,
     attrs: Vec<syn::Attribute>,
     name_ident: syn::Ident,
+    packed: bool,
     unused: &mut UnusedDiagnostic,
 ) -> Result<TokenStream, syn::Error> {
     // Parse #[redact(all, variant, ...)] from the enum attributes, if present.
     let top_level_flags = match attrs.len() {
         0 => None,
-        1 => match FieldFlags::extract::<1>("Redact", &attrs, FieldFlagsParse { skip_allowed: false })? {
+        1 => match FieldFlags::extract::<1>(
+            "Redact",
+            &attrs,
+            FieldFlagsParse {
+                skip_allowed: false,
+                transparent_allowed: true,
+                no_display_allowed: false,
+            },
+        )? {
             [Some(flags)] => {
                 if flags.variant {
                     return Err(syn::Error::new(
                         attrs[0].span(),
-                        "`#[redact(variant, ...)]` is invalid for structs",
+                        "`variant` only applies to enums; to redact all struct fields use `#[redact(all)]`",
                     ));
-                } else if !flags.all {
+                } else if flags.rename.is_some() {
+                    return Err(syn::Error::new(
+                        attrs[0].span(),
+                        "`rename` is only valid on named struct fields",
+                    ));
+                } else if flags.order.is_some() {
+                    return Err(syn::Error::new(attrs[0].span(), "`order` is only valid on fields"));
+                } else if !flags.extra_fields.is_empty()
+                    && (flags.transparent || !matches!(s.fields, syn::Fields::Named(_)))
+                {
+                    return Err(syn::Error::new(
+                        attrs[0].span(),
+                        "`extra_field` is only valid on structs with named fields",
+                    ));
+                } else if flags.deref_transparent {
+                    // Only modifiers that change how the `Deref` target is redacted make sense here.
+                    let valid_flags = FieldFlags {
+                        deref_transparent: true,
+                        display: flags.display,
+                        catch: flags.catch,
+                        redact: flags.redact.clone(),
+                        ..Default::default()
+                    };
+                    if flags != valid_flags {
+                        return Err(syn::Error::new(
+                            attrs[0].span(),
+                            "`deref_transparent` only accepts `display`, `catch` and redaction modifiers",
+                        ));
+                    } else if let Some(attr) = s.fields.iter().flat_map(|field| &field.attrs).next() {
+                        return Err(syn::Error::new(
+                            attr.span(),
+                            "fields aren't shown with `#[redact(deref_transparent)]`, so they can't be redacted",
+                        ));
+                    }
+                    Some(flags)
+                } else if flags.opaque {
+                    // Nothing but the struct's name is shown, so only modifiers for the marker itself make sense here.
+                    let valid_flags = FieldFlags {
+                        opaque: true,
+                        all: flags.all,
+                        redact: RedactFlags {
+                            redact_length: match flags.redact.redact_length {
+                                RedactionLength::Fixed(_) => flags.redact.redact_length,
+                                _ => RedactionLength::Full,
+                            },
+                            redact_char: flags.redact.redact_char,
+                            redact_pattern: flags.redact.redact_pattern.clone(),
+                            cycle: flags.redact.cycle,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    };
+                    if flags != valid_flags {
+                        return Err(syn::Error::new(
+                            attrs[0].span(),
+                            "`opaque` only accepts `all`, `fixed` and `with`",
+                        ));
+                    } else if let Some(attr) = s.fields.iter().flat_map(|field| &field.attrs).next() {
+                        return Err(syn::Error::new(
+                            attr.span(),
+                            "fields aren't shown with `#[redact(opaque)]`, so they can't be redacted",
+                        ));
+                    }
+                    Some(flags)
+                } else if flags.transparent && s.fields.len() != 1 {
+                    return Err(syn::Error::new(
+                        attrs[0].span(),
+                        "`#[redact(transparent)]` is only valid for structs with exactly one field",
+                    ));
+                } else if !flags.all
+                    && !flags.transparent
+                    && flags.extra_fields.is_empty()
+                    && (flags.max_depth.is_none()
+                        || flags
+                            != (FieldFlags {
+                                max_depth: flags.max_depth,
+                                ..Default::default()
+                            }))
+                {
                     return Err(syn::Error::new(
                         attrs[0].span(),
                         "at least `#[redact(all)]` is required here to redact all struct fields",
@@ -43,17 +131,49 @@ pub(super) fn derive_redact(
         }
     };
 
+    // `#[redact(deref_transparent)]` redacts the `Deref` target with the container's flags instead of any fields.
+    let deref_transparent = top_level_flags
+        .as_ref()
+        .filter(|flags| flags.deref_transparent)
+        .map(|flags| FieldFlags {
+            deref_transparent: false,
+            ..flags.clone()
+        });
+
+    // `#[redact(opaque)]` shows a single marker of `fixed` width, one redaction character by default.
+    let opaque = top_level_flags
+        .as_ref()
+        .filter(|flags| flags.opaque)
+        .map(|flags| match flags.redact.redact_length {
+            RedactionLength::Fixed(_) => flags.redact.clone(),
+            _ => RedactFlags {
+                redact_length: RedactionLength::Fixed(NonZeroU8::MIN),
+                ..flags.redact.clone()
+            },
+        });
+
+    let depth_guard = generate_depth_guard(top_level_flags.as_ref().and_then(|flags| flags.max_depth));
+
+    // `#[redact(transparent)]` on its own only removes the wrapper, it doesn't redact the field.
+    let transparent = top_level_flags.as_ref().is_some_and(|flags| flags.transparent);
+    let extra_fields = top_level_flags
+        .as_ref()
+        .map(|flags| flags.extra_fields.clone())
+        .unwrap_or_default();
+    let top_level_flags = top_level_flags.filter(|flags| flags.all).map(|flags| FieldFlags {
+        transparent: false,
+        extra_fields: Vec::new(),
+        max_depth: None,
+        ..flags
+    });
+
     // Convert the name of this struct into a string for use as the first argument to `.debug_struct` or `.debug_tuple`.
-    let name_ident_str = name_ident.to_string().into_token_stream();
+    let name = name_ident.to_string();
+    let name_ident_str = name.to_token_stream();
 
-    // Generate the body of the std::fmt::Debug implementation
-    let impl_debug = match &s.fields {
-        syn::Fields::Named(named) => {
-            FormatData::FieldsNamed(named).impl_debug(name_ident_str, top_level_flags, true, unused)?
-        }
-        syn::Fields::Unnamed(unnamed) => {
-            FormatData::FieldsUnnamed(unnamed).impl_debug(name_ident_str, top_level_flags, true, unused)?
-        }
+    let data = match &s.fields {
+        syn::Fields::Named(named) => FormatData::FieldsNamed(named),
+        syn::Fields::Unnamed(unnamed) => FormatData::FieldsUnnamed(unnamed),
         syn::Fields::Unit => {
             return Err(syn::Error::new(
                 name_ident.span(),
@@ -62,10 +182,46 @@ pub(super) fn derive_redact(
         }
     };
 
+    // Fields of `#[repr(packed)]` structs may be unaligned, so they're copied out before they're formatted, like
+    // `#[derive(Debug)]` does. This requires the fields to be `Copy`.
+    let access = if packed {
+        FieldAccess::SelfCopy
+    } else {
+        FieldAccess::SelfRef
+    };
+
+    // Generate the body of the std::fmt::Debug implementation
+    let impl_debug = if let Some(flags) = deref_transparent {
+        // The target is borrowed again so that unsized targets such as `str` or `[T]` can be formatted as `&dyn Debug`.
+        let body = generate_redact_call(quote! { &::std::ops::Deref::deref(self) }, &name, None, &flags, unused);
+        quote! {
+            ::std::fmt::Debug::fmt(#body, fmt)?
+        }
+    } else if let Some(flags) = opaque {
+        // None of the fields are formatted, so they don't even need to implement `Debug`.
+        unused.redacted_something();
+        quote! {
+            fmt.debug_tuple(#name_ident_str)
+                .field(&veil::private::OpaqueMarker(veil::private::RedactFlags { #flags }))
+                .finish()?
+        }
+    } else if transparent {
+        // Format the single field as if it were the struct itself, without the `Name(...)` wrapper.
+        let FormattedFields { prelude, bodies, .. } = data.fields(&name, top_level_flags, access, unused)?;
+        quote! {
+            #(#prelude)*
+            #(::std::fmt::Debug::fmt(&#bodies, fmt)?)*
+        }
+    } else {
+        data.impl_debug(name_ident_str, &name, top_level_flags, &extra_fields, access, unused)?
+    };
+
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     Ok(quote! {
         impl #impl_generics ::std::fmt::Debug for #name_ident #ty_generics #where_clause {
             fn fmt(&self, fmt: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                #depth_guard
+
                 #[allow(unused)] // Suppresses unused warning with `#[redact(display)]`
                 let alternate = fmt.alternate();
 
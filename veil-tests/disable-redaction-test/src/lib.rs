@@ -10,3 +10,46 @@ fn test_veil_can_be_disabled() {
     veil::disable().ok();
     assert_has_sensitive_data(SensitiveWrapper(SENSITIVE_DATA[0].to_string()));
 }
+
+#[test]
+fn test_redactor_can_be_disabled() {
+    veil::disable().ok();
+
+    let redactor = veil::redactor::Redactor::builder().partial().build().unwrap();
+    assert_eq!(redactor.redact(SENSITIVE_DATA[0].to_string()), SENSITIVE_DATA[0]);
+    assert_eq!(redactor.redact_display(&SENSITIVE_DATA[1]), SENSITIVE_DATA[1]);
+    assert_eq!(
+        format!("{:?}", redactor.wrap(&SENSITIVE_DATA[2])),
+        format!("{:?}", SENSITIVE_DATA[2])
+    );
+}
+
+#[test]
+fn test_secret_fields_stay_redacted() {
+    #[derive(Redact)]
+    struct Credentials {
+        #[redact(partial)]
+        username: String,
+        #[redact(secret)]
+        password: String,
+        #[redact(secret, with_fn_ctx = redact_token)]
+        token: String,
+    }
+
+    fn redact_token(_field: &str, value: &str, redactor: &veil::redactor::Redactor) -> String {
+        redactor.redact(value.to_string())
+    }
+
+    veil::disable().ok();
+
+    let credentials = Credentials {
+        username: SENSITIVE_DATA[0].to_string(),
+        password: SENSITIVE_DATA[1].to_string(),
+        token: SENSITIVE_DATA[2].to_string(),
+    };
+    for debug in [format!("{credentials:?}"), format!("{credentials:#?}")] {
+        assert!(debug.contains(SENSITIVE_DATA[0]), "{debug}");
+        assert!(!debug.contains(SENSITIVE_DATA[1]), "{debug}");
+        assert!(!debug.contains(SENSITIVE_DATA[2]), "{debug}");
+    }
+}
@@ -0,0 +1,82 @@
+#![cfg_attr(not(test), allow(unused))]
+//! Tests the defaults set by the `profile-*` features. Run once for each profile, e.g. with `--features strict`, and
+//! without any, which must behave like `profile-standard`.
+
+use veil::{redactor::Redactor, Redact, RedactExt};
+
+/// The expected redaction of each input, fully and partially redacted.
+struct Expected {
+    full: &'static str,
+    partial: [&'static str; 3],
+}
+
+#[cfg(feature = "lenient")]
+const EXPECTED: Expected = Expected {
+    full: "**** ***",
+    partial: ["J**n", "Wi***am", "john.***@***ma.it"],
+};
+
+#[cfg(not(any(feature = "lenient", feature = "strict")))]
+const EXPECTED: Expected = Expected {
+    full: "**** ***",
+    partial: ["****", "Wi***am", "joh*.***@****a.it"],
+};
+
+#[cfg(feature = "strict")]
+const EXPECTED: Expected = Expected {
+    full: "#### ###",
+    partial: ["####", "#######", "jo##.###@#####.it"],
+};
+
+const PARTIAL: [&str; 3] = ["John", "William", "john.doe@prima.it"];
+
+#[test]
+fn test_profile_derive() {
+    #[derive(Redact)]
+    struct Customer {
+        #[redact(display)]
+        name: &'static str,
+        #[redact(partial, display)]
+        email: &'static str,
+    }
+
+    assert_eq!(
+        format!(
+            "{:?}",
+            Customer {
+                name: "John Doe",
+                email: PARTIAL[0],
+            }
+        ),
+        format!("Customer {{ name: {}, email: {} }}", EXPECTED.full, EXPECTED.partial[0])
+    );
+
+    for (data, expected) in PARTIAL.into_iter().zip(EXPECTED.partial) {
+        let customer = Customer { name: "", email: data };
+        assert_eq!(
+            format!("{customer:?}"),
+            format!("Customer {{ name: , email: {expected} }}")
+        );
+    }
+}
+
+#[test]
+fn test_profile_redactor() {
+    let full = Redactor::builder().build().unwrap();
+    let partial = Redactor::builder().partial().build().unwrap();
+    let parsed: Redactor = "partial".parse().unwrap();
+
+    assert_eq!(full.redact("John Doe".to_string()), EXPECTED.full);
+    for (data, expected) in PARTIAL.into_iter().zip(EXPECTED.partial) {
+        assert_eq!(partial.redact(data.to_string()), expected);
+        assert_eq!(parsed.redact(data.to_string()), expected);
+    }
+}
+
+#[test]
+fn test_profile_ext() {
+    assert_eq!("John Doe".redacted().to_string(), EXPECTED.full);
+    for (data, expected) in PARTIAL.into_iter().zip(EXPECTED.partial) {
+        assert_eq!(data.redacted_partial().to_string(), expected);
+    }
+}
@@ -0,0 +1,100 @@
+#![cfg_attr(not(test), allow(unused))]
+//! Tests that the `redact-noop` feature passes every redacted field through as plaintext
+
+use veil::{Redact, Redactable};
+use veil_tests::{assert_has_sensitive_data, SENSITIVE_DATA};
+
+#[test]
+fn test_redact_noop_passes_through() {
+    #[derive(Redact)]
+    #[redact(all, partial)]
+    struct Customer {
+        name: String,
+        #[redact(fixed = 3)]
+        phone: &'static str,
+        #[redact(display)]
+        address: &'static str,
+        #[redact(type_only)]
+        tags: Vec<u8>,
+        #[redact(skip)]
+        id: u64,
+    }
+
+    #[derive(Redact)]
+    #[redact(all, variant)]
+    enum Policy {
+        #[redact(variant, partial)]
+        SensitiveVariant(#[redact] &'static str, u8),
+        Empty,
+    }
+
+    #[derive(Redact)]
+    struct Wrapper {
+        customer: Customer,
+        #[redact(redactable)]
+        card: Card,
+    }
+
+    #[derive(Redactable)]
+    #[redact(partial)]
+    struct Card(&'static str);
+    impl std::fmt::Display for Card {
+        fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            fmt.write_str(self.0)
+        }
+    }
+
+    let customer = Customer {
+        name: SENSITIVE_DATA[0].to_string(),
+        phone: SENSITIVE_DATA[2],
+        address: SENSITIVE_DATA[3],
+        tags: vec![1, 2],
+        id: 42,
+    };
+    assert_eq!(
+        format!("{customer:?}"),
+        "Customer { name: \"William\", phone: \"039845734895\", address: 10 Downing Street, tags: [1, 2], id: 42 }"
+    );
+    assert_has_sensitive_data(&customer);
+
+    assert_eq!(
+        format!("{:?}", Policy::SensitiveVariant(SENSITIVE_DATA[1], 7)),
+        "SensitiveVariant(\"Assicurazioni\", 7)"
+    );
+    assert_eq!(format!("{:?}", Policy::Empty), "Empty");
+
+    let wrapper = Wrapper {
+        customer,
+        card: Card(SENSITIVE_DATA[2]),
+    };
+    assert_eq!(Card(SENSITIVE_DATA[2]).redact(), SENSITIVE_DATA[2]);
+    assert_has_sensitive_data(wrapper);
+}
+
+#[test]
+fn test_redact_noop_redact_impl() {
+    struct Customer {
+        name: &'static str,
+        address: &'static str,
+    }
+
+    #[veil::redact_impl]
+    impl std::fmt::Debug for Customer {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            let mut s = f.debug_struct("Customer");
+            veil_field!(s, "name", &self.name, partial);
+            veil_field!(s, "address", &self.address, display);
+            s.finish()
+        }
+    }
+
+    let customer = Customer {
+        name: SENSITIVE_DATA[0],
+        address: SENSITIVE_DATA[3],
+    };
+    assert_eq!(
+        format!("{customer:?}"),
+        "Customer { name: \"William\", address: 10 Downing Street }"
+    );
+    assert_has_sensitive_data(customer);
+}
@@ -13,7 +13,6 @@ fail_tests! {
     redact_all_variant_on_variant,
     redact_enum_without_variant,
     redact_too_many,
-    redact_unused,
     redact_variant_on_field,
     redact_variant_on_struct,
     redact_union,
@@ -27,5 +26,65 @@ fail_tests! {
     redactable_multiple_fields,
     redactable_non_struct,
     redact_all_with_value,
-    redactable_unknown_flag
+    redactable_unknown_flag,
+    redact_impl_invalid,
+    redact_impl_unsupported,
+    redact_rename_invalid,
+    redact_transparent_invalid,
+    redact_cfg_invalid,
+    redact_policy_invalid,
+    redact_type_only_invalid,
+    redact_no_display_invalid,
+    redact_order_invalid,
+    redact_extra_field_invalid,
+    redact_as_invalid,
+    redact_expose_pct_invalid,
+    redact_expose_invalid,
+    redact_min_invalid,
+    redact_expose_side_invalid,
+    redact_trust_inner_invalid,
+    redact_cycle_invalid,
+    redact_cjk_invalid,
+    redact_preserve_class_invalid,
+    redact_deref_transparent_invalid,
+    redact_mask_ends_invalid,
+    redact_pad_invalid,
+    redact_opaque_invalid,
+    redact_mask_none_invalid,
+    redact_try_display_invalid,
+    redact_values_invalid,
+    redact_max_depth_invalid,
+    redact_hash_invalid
+}
+
+// The interop features implement `Redactable` for more types, which changes the suggestions in the expected errors.
+#[cfg(not(any(feature = "uuid", feature = "url", feature = "chrono")))]
+fail_tests! {
+    redact_redactable_invalid
+}
+
+// Without the `hash` feature, `#[redact(hash = "...")]` is rejected rather than silently masking instead.
+#[cfg(not(feature = "hash"))]
+fail_tests! {
+    redact_hash_disabled
+}
+
+// The `strict` feature intentionally changes which error is reported for dead redaction config.
+#[cfg(not(feature = "strict"))]
+fail_tests! {
+    redact_unused,
+    display_fixed
+}
+
+#[cfg(feature = "strict")]
+fail_tests! {
+    strict_all_skipped,
+    strict_redundant,
+    strict_display_fixed
+}
+
+// The `pedantic` feature only emits warnings, so this test denies them to make them visible.
+#[cfg(feature = "pedantic")]
+fail_tests! {
+    pedantic_bool
 }
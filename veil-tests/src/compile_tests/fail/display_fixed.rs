@@ -0,0 +1,15 @@
+#![deny(deprecated)]
+
+fn main() {}
+
+#[derive(veil::Redact)]
+struct Foo {
+    #[redact(display, fixed = 3)]
+    bar: String,
+}
+
+#[derive(veil::Redact)]
+enum Baz {
+    #[redact(all, fixed = 3, display)]
+    Qux(String),
+}
@@ -0,0 +1,17 @@
+#![deny(deprecated)]
+
+fn main() {}
+
+#[derive(veil::Redact)]
+struct Foo {
+    #[redact]
+    bar: bool,
+
+    #[redact(partial)]
+    baz: String,
+}
+
+#[derive(veil::Redact)]
+enum Qux {
+    Quux(#[redact] ()),
+}
@@ -0,0 +1,7 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+struct Foo {
+    #[redact(as = "HashMap")]
+    bar: std::collections::HashMap<String, String>,
+}
@@ -0,0 +1,20 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+enum Foo {
+    #[redact(variant, cfg(feature = "prod-logging"))]
+    Bar,
+}
+
+#[derive(veil::Redact)]
+#[redact(all)]
+struct Baz {
+    #[redact(skip, cfg(feature = "prod-logging"))]
+    qux: String,
+}
+
+#[derive(veil::Redact)]
+struct Quux {
+    #[redact(cfg = "prod-logging")]
+    corge: String,
+}
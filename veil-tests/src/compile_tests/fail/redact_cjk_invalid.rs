@@ -0,0 +1,7 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+struct Foo {
+    #[redact(cjk)]
+    bar: String,
+}
@@ -0,0 +1,25 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+struct Foo {
+    #[redact(with = "XY")]
+    bar: String,
+}
+
+#[derive(veil::Redact)]
+struct Baz {
+    #[redact(with = 'X', cycle)]
+    qux: String,
+}
+
+#[derive(veil::Redact)]
+struct Quux {
+    #[redact(with = "", cycle)]
+    corge: String,
+}
+
+#[derive(veil::Redact)]
+struct Grault {
+    #[redact(with = 1, cycle)]
+    garply: String,
+}
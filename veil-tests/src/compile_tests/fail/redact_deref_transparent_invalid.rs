@@ -0,0 +1,15 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+#[redact(all, deref_transparent)]
+struct Foo(Vec<String>);
+
+#[derive(veil::Redact)]
+#[redact(deref_transparent)]
+struct Bar(#[redact] Vec<String>);
+
+#[derive(veil::Redact)]
+enum Baz {
+    #[redact(all, deref_transparent)]
+    Qux(Vec<String>),
+}
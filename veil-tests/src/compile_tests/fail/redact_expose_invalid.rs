@@ -0,0 +1,19 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+struct Foo {
+    #[redact(partial, expose = 0)]
+    bar: String,
+}
+
+#[derive(veil::Redact)]
+struct Baz {
+    #[redact(expose = 4)]
+    qux: String,
+}
+
+#[derive(veil::Redact)]
+struct Quux {
+    #[redact(partial, expose = 4, expose_pct = 10)]
+    corge: String,
+}
@@ -0,0 +1,17 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+struct Foo {
+    #[redact(partial, expose_pct = 50)]
+    bar: String,
+}
+
+#[derive(veil::Redact)]
+struct Baz {
+    #[redact(expose_pct = 10)]
+    qux: String,
+}
+
+#[derive(veil::Redactable)]
+#[redact(fixed = 3, expose_pct = 10)]
+struct Quux(String);
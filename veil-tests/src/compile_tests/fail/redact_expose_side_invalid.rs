@@ -0,0 +1,19 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+struct Foo {
+    #[redact(expose_end)]
+    bar: String,
+}
+
+#[derive(veil::Redact)]
+struct Baz {
+    #[redact(partial, expose_start, expose_end)]
+    qux: String,
+}
+
+#[derive(veil::Redact)]
+struct Quux {
+    #[redact(partial, cjk, expose_start)]
+    corge: String,
+}
@@ -0,0 +1,51 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+#[redact(extra_field(name = "computed", source = "computed"))]
+struct Foo {
+    #[redact]
+    bar: String,
+}
+
+#[derive(veil::Redact)]
+#[redact(extra_field(source = "self.computed"))]
+struct Baz {
+    #[redact]
+    qux: String,
+}
+
+#[derive(veil::Redact)]
+#[redact(extra_field(name = "computed", source = "self.computed", skip))]
+struct Quux {
+    #[redact]
+    corge: String,
+}
+
+#[derive(veil::Redact)]
+#[redact(extra_field(name = "computed", source = "self.computed"))]
+struct Grault(#[redact] String);
+
+#[derive(veil::Redact)]
+struct Garply {
+    #[redact(extra_field(name = "computed", source = "self.computed"))]
+    waldo: String,
+}
+
+#[derive(veil::Redact)]
+#[redact(all, variant, extra_field(name = "computed", source = "self.computed"))]
+enum Fred {
+    Plugh,
+}
+
+#[derive(veil::Redact)]
+enum Xyzzy {
+    #[redact(all, extra_field(name = "computed", source = "self.computed"))]
+    Thud(String),
+}
+
+#[derive(veil::Redact)]
+#[redact(extra_field(name = "computed", source = "self.missing"))]
+struct Missing {
+    #[redact]
+    field: String,
+}
@@ -0,0 +1,20 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+struct Foo {
+    #[redact(hash = "sha256")]
+    bar: String,
+}
+
+struct Baz {
+    qux: String,
+}
+
+#[veil::redact_impl]
+impl std::fmt::Debug for Baz {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = f.debug_struct("Baz");
+        veil_field!(s, "qux", &self.qux, hash = "sha256");
+        s.finish()
+    }
+}
@@ -0,0 +1,31 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+struct Foo {
+    #[redact(hash = "md5")]
+    bar: String,
+}
+
+#[derive(veil::Redact)]
+struct Baz {
+    #[redact(hash = "sha256", partial)]
+    qux: String,
+}
+
+#[derive(veil::Redact)]
+struct Quux {
+    #[redact(hash = "sha256", fixed = 3)]
+    corge: String,
+}
+
+#[derive(veil::Redact)]
+struct Grault {
+    #[redact(hash = "sha256", with = 'X')]
+    garply: String,
+}
+
+#[derive(veil::Redact)]
+struct Waldo {
+    #[redact(hash = "sha256", with = '*')]
+    fred: String,
+}
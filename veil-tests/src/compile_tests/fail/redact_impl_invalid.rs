@@ -0,0 +1,39 @@
+fn main() {}
+
+#[veil::redact_impl(partial)]
+fn foo() {}
+
+#[veil::redact_impl]
+struct Bar;
+
+struct Baz {
+    qux: String,
+}
+
+#[veil::redact_impl]
+impl std::fmt::Debug for Baz {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = f.debug_struct("Baz");
+        veil_field!(s, "qux", &self.qux, skip);
+        s.finish()
+    }
+}
+
+struct Quux {
+    corge: String,
+}
+
+fn redact_by_name(_field: &str, value: &str, redactor: &veil::redactor::Redactor) -> String {
+    redactor.redact(value.to_string())
+}
+
+#[veil::redact_impl]
+impl std::fmt::Debug for Quux {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = f.debug_struct("Quux");
+        veil_field!(s, "corge", &self.corge, with_fn_ctx = redact_by_name);
+        veil_field!(s, "grault", &self.corge, type_only);
+        veil_field!(s, "garply", &self.corge, try_display = "self.to_string");
+        s.finish()
+    }
+}
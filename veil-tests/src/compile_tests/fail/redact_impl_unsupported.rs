@@ -0,0 +1,22 @@
+fn main() {}
+
+struct Foo {
+    bar: Option<String>,
+}
+
+#[veil::redact_impl]
+impl std::fmt::Debug for Foo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = f.debug_struct("Foo");
+        veil_field!(s, "expose_pct", &self.bar, expose_pct = 40);
+        veil_field!(s, "as", &self.bar, as = "Option", partial);
+        veil_field!(s, "values", &self.bar, values);
+        veil_field!(s, "redactable", &self.bar, redactable);
+        veil_field!(s, "mask_none", &self.bar, mask_none);
+        veil_field!(s, "no_display", &self.bar, no_display);
+        veil_field!(s, "opaque", &self.bar, opaque);
+        veil_field!(s, "transparent", &self.bar, transparent);
+        veil_field!(s, "deref_transparent", &self.bar, deref_transparent);
+        s.finish()
+    }
+}
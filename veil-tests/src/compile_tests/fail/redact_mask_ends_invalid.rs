@@ -0,0 +1,7 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+struct Foo {
+    #[redact(partial, mask_prefix = 2)]
+    bar: String,
+}
@@ -0,0 +1,19 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+struct Foo {
+    #[redact(mask_none)]
+    bar: String,
+}
+
+#[derive(veil::Redact)]
+struct Baz {
+    #[redact(mask_none, partial)]
+    qux: Option<String>,
+}
+
+#[derive(veil::Redact)]
+enum Quux {
+    #[redact(variant, mask_none)]
+    Corge,
+}
@@ -0,0 +1,27 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+#[redact(max_depth = 0)]
+struct Foo {
+    #[redact]
+    bar: String,
+}
+
+#[derive(veil::Redact)]
+struct Baz {
+    #[redact(max_depth = 2)]
+    qux: String,
+}
+
+#[derive(veil::Redact)]
+enum Quux {
+    #[redact(all, max_depth = 2)]
+    Corge(String),
+}
+
+#[derive(veil::Redact)]
+#[redact(max_depth = 2, partial)]
+struct Grault {
+    #[redact]
+    garply: String,
+}
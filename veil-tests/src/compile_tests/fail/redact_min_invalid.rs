@@ -0,0 +1,19 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+struct Foo {
+    #[redact(partial, min = 0)]
+    bar: String,
+}
+
+#[derive(veil::Redact)]
+struct Baz {
+    #[redact(min = 3)]
+    qux: String,
+}
+
+#[derive(veil::Redact)]
+struct Quux {
+    #[redact(partial, min = 5, expose = 3)]
+    corge: String,
+}
@@ -0,0 +1,21 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+#[redact(all, display)]
+struct Foo {
+    #[redact(display, no_display)]
+    bar: String,
+}
+
+#[derive(veil::Redact)]
+#[redact(all)]
+struct Baz {
+    #[redact(no_display)]
+    qux: String,
+}
+
+#[derive(veil::Redact)]
+#[redact(all, no_display)]
+struct Quux {
+    corge: String,
+}
@@ -0,0 +1,26 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+#[redact(opaque, partial)]
+struct Foo {
+    bar: String,
+}
+
+#[derive(veil::Redact)]
+#[redact(opaque)]
+struct Baz {
+    #[redact]
+    qux: String,
+}
+
+#[derive(veil::Redact)]
+#[redact(opaque)]
+enum Quux {
+    Corge(String),
+}
+
+#[derive(veil::Redact)]
+struct Grault {
+    #[redact(opaque)]
+    garply: String,
+}
@@ -0,0 +1,19 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+#[redact(all, order = 1)]
+struct Foo {
+    bar: String,
+}
+
+#[derive(veil::Redact)]
+enum Baz {
+    #[redact(all, order = 1)]
+    Qux(String),
+}
+
+#[derive(veil::Redact)]
+struct Quux {
+    #[redact(order = "first")]
+    corge: String,
+}
@@ -0,0 +1,19 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+struct Foo {
+    #[redact(pad = 0)]
+    bar: String,
+}
+
+#[derive(veil::Redact)]
+struct Baz {
+    #[redact(pad = 8, align = "justify")]
+    qux: String,
+}
+
+#[derive(veil::Redact)]
+struct Quux {
+    #[redact(align = "right")]
+    corge: String,
+}
@@ -0,0 +1,20 @@
+fn main() {}
+
+const POLICY: veil::redactor::Redactor = match veil::redactor::Redactor::builder().partial().build() {
+    Ok(policy) => policy,
+    Err(_) => panic!(),
+};
+
+const NOT_A_POLICY: char = 'X';
+
+#[derive(veil::Redact)]
+struct Foo {
+    #[redact(policy = POLICY, partial)]
+    bar: String,
+}
+
+#[derive(veil::Redact)]
+struct Quux {
+    #[redact(policy = NOT_A_POLICY)]
+    corge: String,
+}
@@ -0,0 +1,25 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+struct Foo {
+    #[redact(preserve_class = "x")]
+    bar: String,
+}
+
+#[derive(veil::Redact)]
+struct Baz {
+    #[redact(preserve_class, fixed = 3)]
+    qux: String,
+}
+
+#[derive(veil::Redact)]
+struct Quux {
+    #[redact(preserve_class, with = 'X')]
+    corge: String,
+}
+
+#[derive(veil::Redact)]
+struct Grault {
+    #[redact(preserve_class, with = '*')]
+    garply: String,
+}
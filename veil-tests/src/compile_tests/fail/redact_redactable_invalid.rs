@@ -0,0 +1,21 @@
+fn main() {}
+
+#[derive(veil::Redactable)]
+struct Email(String);
+impl std::fmt::Display for Email {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[derive(veil::Redact)]
+struct Foo {
+    #[redact(redactable, partial)]
+    bar: Email,
+}
+
+#[derive(veil::Redact)]
+struct Bar {
+    #[redact(redactable)]
+    baz: String,
+}
@@ -0,0 +1,22 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+struct Foo(#[redact(rename = "bar")] String);
+
+#[derive(veil::Redact)]
+#[redact(all, rename = "bar")]
+struct Bar {
+    baz: String,
+}
+
+#[derive(veil::Redact)]
+#[redact(all, variant, rename = "bar")]
+enum Baz {
+    Qux,
+}
+
+#[derive(veil::Redact)]
+enum Qux {
+    #[redact(variant, rename = "bar")]
+    Quux,
+}
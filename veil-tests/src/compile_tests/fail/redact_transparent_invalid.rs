@@ -0,0 +1,27 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+#[redact(transparent)]
+struct Foo(#[redact] String, String);
+
+#[derive(veil::Redact)]
+#[redact(all, transparent)]
+struct Bar {
+    baz: String,
+    qux: String,
+}
+
+#[derive(veil::Redact)]
+struct Baz(#[redact(transparent)] String);
+
+#[derive(veil::Redact)]
+#[redact(all, variant, transparent)]
+enum Qux {
+    Quux(String),
+}
+
+#[derive(veil::Redact)]
+enum Quux {
+    #[redact(transparent)]
+    Corge(#[redact] String),
+}
@@ -0,0 +1,25 @@
+fn main() {}
+
+#[derive(Debug)]
+struct Card;
+
+#[derive(veil::Redact)]
+#[redact(all, trust_inner())]
+struct Foo {
+    card: Card,
+    name: String,
+}
+
+#[derive(veil::Redact)]
+#[redact(trust_inner(Card))]
+struct Bar {
+    card: Card,
+    #[redact]
+    name: String,
+}
+
+#[derive(veil::Redact)]
+#[redact(all, variant, trust_inner(Card))]
+enum Baz {
+    Qux { card: Card },
+}
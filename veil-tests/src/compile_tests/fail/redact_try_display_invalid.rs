@@ -0,0 +1,42 @@
+fn main() {}
+
+struct Encrypted;
+impl Encrypted {
+    fn fmt_safe(&self) -> Result<String, ()> {
+        Err(())
+    }
+
+    fn fmt_unchecked(&self) -> String {
+        String::new()
+    }
+}
+
+#[derive(veil::Redact)]
+struct Foo {
+    #[redact(try_display = "fmt_safe")]
+    bar: Encrypted,
+}
+
+#[derive(veil::Redact)]
+struct Baz {
+    #[redact(display, try_display = "self.fmt_safe")]
+    qux: Encrypted,
+}
+
+#[derive(veil::Redact)]
+#[redact(all, try_display = "self.fmt_safe")]
+struct Quux {
+    corge: Encrypted,
+}
+
+#[derive(veil::Redact)]
+struct Grault {
+    #[redact(try_display = "self.fmt_unchecked")]
+    garply: Encrypted,
+}
+
+#[derive(veil::Redact)]
+struct Waldo {
+    #[redact(try_display = "self.missing")]
+    fred: Encrypted,
+}
@@ -0,0 +1,13 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+struct Foo {
+    #[redact(type_only, partial)]
+    bar: String,
+}
+
+#[derive(veil::Redact)]
+enum Baz {
+    #[redact(variant, type_only)]
+    Qux,
+}
@@ -0,0 +1,33 @@
+fn main() {}
+
+type Changes = std::collections::HashMap<String, String>;
+
+#[derive(veil::Redact)]
+struct Foo {
+    #[redact(values)]
+    bar: Vec<String>,
+}
+
+#[derive(veil::Redact)]
+struct Baz {
+    #[redact(values)]
+    qux: Changes,
+}
+
+#[derive(veil::Redact)]
+struct Quux {
+    #[redact(as = "Map")]
+    corge: Changes,
+}
+
+#[derive(veil::Redact)]
+struct Grault {
+    #[redact(values, type_only)]
+    garply: Changes,
+}
+
+#[derive(veil::Redact)]
+#[redact(all, values)]
+struct Waldo {
+    fred: Changes,
+}
@@ -6,3 +6,15 @@ struct Foo {
     #[redact]
     bar: String,
 }
+
+#[derive(veil::Redact)]
+#[redact(variant)]
+struct Bar(String);
+
+#[derive(veil::Redact)]
+#[redact(all, variant, partial)]
+struct Baz(String, String);
+
+#[derive(veil::Redact)]
+#[redact(all, variant)]
+struct Qux;
@@ -0,0 +1,32 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+#[redact(all)]
+struct Foos {
+    #[redact(skip)]
+    bar: String,
+
+    #[redact(skip)]
+    baz: String,
+}
+
+#[derive(veil::Redact)]
+enum Fooe {
+    #[redact(all)]
+    Bar {
+        #[redact(skip)]
+        baz: String,
+    },
+
+    Qux(#[redact] String),
+}
+
+#[derive(veil::Redact)]
+#[redact(all, variant)]
+enum Foov {
+    #[redact(variant, skip)]
+    Bar(#[redact] String),
+
+    #[redact(variant, skip)]
+    Baz,
+}
@@ -0,0 +1,11 @@
+fn main() {}
+
+#[derive(veil::Redact)]
+struct Foo {
+    #[redact(display, fixed = 3)]
+    bar: String,
+}
+
+#[derive(veil::Redact)]
+#[redact(all, fixed = 3, display)]
+struct Baz(String);
@@ -0,0 +1,21 @@
+#![deny(deprecated)]
+
+fn main() {}
+
+#[derive(veil::Redact)]
+#[redact(all, partial)]
+struct Foos {
+    #[redact(partial)]
+    bar: String,
+
+    baz: String,
+}
+
+#[derive(veil::Redact)]
+enum Fooe {
+    #[redact(all, with = 'X')]
+    Bar {
+        #[redact(with = 'X')]
+        baz: String,
+    },
+}
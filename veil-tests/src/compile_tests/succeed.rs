@@ -72,6 +72,151 @@ struct RedactAllWithFlagsDisplay {
     field3: String,
 }
 
+#[derive(Redact)]
+#[redact(all, partial, display)]
+struct RedactAllDisplayWithNoDisplay {
+    field: String,
+
+    #[redact(no_display)]
+    field2: Option<String>,
+
+    #[redact(skip)]
+    field3: String,
+
+    #[redact(partial, with = 'X', no_display)]
+    field4: Vec<u8>,
+}
+
+#[derive(Redact)]
+#[redact(all, partial)]
+struct RedactAllWithRename {
+    field: String,
+
+    #[redact(skip, rename = "label")]
+    field2: String,
+
+    #[redact(rename = "label3")]
+    field3: String,
+}
+
+#[derive(Redactable)]
+#[redact(partial, with = '#')]
+struct Email(String);
+impl std::fmt::Display for Email {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[derive(Redact)]
+struct RedactRedactable {
+    #[redact(redactable)]
+    email: Email,
+
+    #[redact]
+    name: String,
+}
+
+#[derive(Redact)]
+enum RedactRedactableEnum {
+    #[redact(all, redactable)]
+    Contact(Email, #[redact(skip)] String),
+}
+
+const CONTACT_POLICY: veil::redactor::Redactor = match veil::redactor::Redactor::builder().char('X').partial().build() {
+    Ok(policy) => policy,
+    Err(_) => panic!("invalid redaction policy"),
+};
+
+#[derive(Redact)]
+struct RedactPolicy {
+    #[redact(policy = CONTACT_POLICY)]
+    email: String,
+
+    #[redact(policy = self::CONTACT_POLICY)]
+    phone: Option<String>,
+}
+
+#[derive(Redact)]
+#[redact(all, variant, policy = CONTACT_POLICY)]
+enum RedactPolicyEnum {
+    Contact { name: String },
+}
+
+/// Redacts fields based on their name, falling back to the configured flags.
+fn redact_by_field_name(field: &str, value: &str, redactor: &veil::redactor::Redactor) -> String {
+    match field {
+        "email" => match value.split_once('@') {
+            Some((user, domain)) => format!("{}@{domain}", redactor.redact(user.to_string())),
+            None => redactor.redact(value.to_string()),
+        },
+        "0" | "age" => "<age>".to_string(),
+        _ => redactor.redact(value.to_string()),
+    }
+}
+
+#[derive(Redact)]
+#[redact(all, with_fn_ctx = redact_by_field_name)]
+struct RedactWithFnCtx {
+    email: String,
+    #[redact(rename = "age", with_fn_ctx = redact_by_field_name)]
+    years: u8,
+    #[redact(partial, with_fn_ctx = redact_by_field_name)]
+    name: String,
+    #[redact(skip)]
+    id: u64,
+}
+
+#[derive(Redact)]
+#[redact(all, variant, with_fn_ctx = redact_by_field_name)]
+enum RedactWithFnCtxEnum {
+    #[redact(all, with_fn_ctx = redact_by_field_name)]
+    Age(u8),
+}
+
+#[derive(Redact)]
+struct CfgGatedFields {
+    #[cfg(test)]
+    #[redact]
+    enabled: String,
+
+    #[cfg(not(test))]
+    #[redact]
+    disabled: String,
+
+    #[redact(partial)]
+    #[cfg_attr(test, allow(unused))]
+    always: String,
+}
+
+#[derive(Redact)]
+enum CfgGatedVariants {
+    #[cfg(test)]
+    #[redact(variant)]
+    Enabled,
+
+    #[cfg(not(test))]
+    #[redact(variant)]
+    Disabled(#[redact] String),
+
+    Tuple(#[cfg(test)] String, #[cfg(not(test))] u8, #[redact] String),
+}
+
+#[derive(Redact)]
+struct RedactCfg {
+    #[redact(cfg(feature = "prod-logging"))]
+    email: String,
+
+    #[redact(partial, cfg(not(feature = "prod-logging")))]
+    name: String,
+}
+
+#[derive(Redact)]
+enum RedactCfgEnum {
+    #[redact(all, cfg(feature = "prod-logging"))]
+    Contact(String, #[redact(skip)] String),
+}
+
 #[derive(Redact)]
 struct RedactNamedDisplay {
     #[redact(display)]
@@ -95,7 +240,7 @@ enum CreditCardIssuer {
     MasterCard,
 
     #[redact(variant)]
-    #[redact(all, fixed = 6, with = '$', display)]
+    #[redact(all, fixed = 6, with = '$')]
     SecretAgentCard {
         secret_data_1: String,
         secret_data_2: String,
@@ -210,12 +355,140 @@ fn test_redact_all_with_flags() {
     );
 }
 
+#[test]
+fn test_redact_all_with_rename() {
+    assert_eq!(
+        format!(
+            "{:?}",
+            RedactAllWithRename {
+                field: "Hello".to_string(),
+                field2: "World".to_string(),
+                field3: "!".to_string(),
+            }
+        ),
+        "RedactAllWithRename { field: \"H***o\", label: \"World\", label3: \"!\" }"
+    );
+}
+
+#[test]
+fn test_redact_redactable() {
+    assert_eq!(
+        format!(
+            "{:?}",
+            RedactRedactable {
+                email: Email("john.doe@prima.it".to_string()),
+                name: "John Doe".to_string(),
+            }
+        ),
+        "RedactRedactable { email: joh#.###@####a.it, name: \"**** ***\" }"
+    );
+    assert_eq!(
+        format!(
+            "{:?}",
+            RedactRedactableEnum::Contact(Email("john.doe@prima.it".to_string()), "John Doe".to_string())
+        ),
+        "Contact(joh#.###@####a.it, \"John Doe\")"
+    );
+}
+
+#[test]
+fn test_redact_policy() {
+    assert_eq!(
+        format!(
+            "{:?}",
+            RedactPolicy {
+                email: "john.doe@prima.it".to_string(),
+                phone: Some("039845734895".to_string()),
+            }
+        ),
+        "RedactPolicy { email: \"johX.XXX@XXXXa.it\", phone: Some(\"039XXXXXX895\") }"
+    );
+    assert_eq!(
+        format!(
+            "{:?}",
+            RedactPolicyEnum::Contact {
+                name: "John Doe".to_string()
+            }
+        ),
+        "CoXXXct { name: \"John Doe\" }"
+    );
+}
+
+#[test]
+fn test_redact_with_fn_ctx() {
+    assert_eq!(
+        format!(
+            "{:?}",
+            RedactWithFnCtx {
+                email: "john.doe@prima.it".to_string(),
+                years: 42,
+                name: "John Doe".to_string(),
+                id: 1,
+            }
+        ),
+        "RedactWithFnCtx { email: \"****.***@prima.it\", age: <age>, name: \"Jo** *oe\", id: 1 }"
+    );
+    assert_eq!(format!("{:?}", RedactWithFnCtxEnum::Age(42)), "***(<age>)");
+}
+
+#[test]
+fn test_cfg_gated_fields() {
+    assert_eq!(
+        format!(
+            "{:?}",
+            CfgGatedFields {
+                enabled: "Hello".to_string(),
+                always: "World!".to_string(),
+            }
+        ),
+        "CfgGatedFields { enabled: \"*****\", always: \"W***d!\" }"
+    );
+    assert_eq!(format!("{:?}", CfgGatedVariants::Enabled), "*******");
+    assert_eq!(
+        format!(
+            "{:?}",
+            CfgGatedVariants::Tuple("Hello".to_string(), "World".to_string())
+        ),
+        "Tuple(\"Hello\", \"*****\")"
+    );
+}
+
+#[test]
+fn test_redact_cfg() {
+    let redacted = RedactCfg {
+        email: "john.doe@prima.it".to_string(),
+        name: "John Doe".to_string(),
+    };
+    let redacted_enum = RedactCfgEnum::Contact("john.doe@prima.it".to_string(), "John Doe".to_string());
+
+    if cfg!(feature = "prod-logging") {
+        assert_eq!(
+            format!("{redacted:?}"),
+            "RedactCfg { email: \"****.***@*****.**\", name: \"John Doe\" }"
+        );
+        assert_eq!(
+            format!("{redacted_enum:?}"),
+            "Contact(\"****.***@*****.**\", \"John Doe\")"
+        );
+    } else {
+        assert_eq!(
+            format!("{redacted:?}"),
+            "RedactCfg { email: \"john.doe@prima.it\", name: \"Jo** *oe\" }"
+        );
+        assert_eq!(
+            format!("{redacted_enum:?}"),
+            "Contact(\"john.doe@prima.it\", \"John Doe\")"
+        );
+    }
+}
+
 #[test]
 fn test_redact_tuple_struct() {
     println!("{:#?}", TupleStruct(100, 2000000));
 }
 
 #[test]
+#[allow(deprecated)] // Redacting `bool`s triggers a warning with the `pedantic` feature
 fn test_redact_multiple_attributes() {
     use rand_derive2::RandGen;
     use serde::{Deserialize, Serialize};
@@ -341,3 +614,524 @@ fn test_redact_multiple_attributes() {
         MultipleAttributesAllTuple
     }
 }
+
+#[derive(Redact)]
+#[redact(
+    all,
+    partial,
+    extra_field(name = "masked", source = "self.masked"),
+    extra_field(name = "len", source = "self.len", fixed = 2, with = '#', catch)
+)]
+struct RedactExtraFields {
+    field: String,
+
+    #[redact(skip)]
+    field2: String,
+}
+impl RedactExtraFields {
+    fn masked(&self) -> &str {
+        &self.field
+    }
+
+    fn len(&self) -> usize {
+        self.field.len()
+    }
+}
+
+#[derive(Redact)]
+#[redact(extra_field(name = "total", source = "self.total", display, digits_only))]
+struct RedactExtraFieldOnly<T: std::ops::Add<Output = T> + Copy + std::fmt::Debug + std::fmt::Display> {
+    a: T,
+    b: T,
+}
+impl<T: std::ops::Add<Output = T> + Copy + std::fmt::Debug + std::fmt::Display> RedactExtraFieldOnly<T> {
+    fn total(&self) -> T {
+        self.a + self.b
+    }
+}
+
+#[derive(Redact)]
+struct RedactWeak {
+    #[redact]
+    field: std::rc::Weak<String>,
+
+    #[redact(partial)]
+    field2: std::sync::Weak<String>,
+}
+
+#[derive(Redact)]
+struct RedactMutRef<'a> {
+    #[redact]
+    name: &'a mut String,
+
+    #[redact(partial, display)]
+    email: &'a mut String,
+
+    #[redact]
+    age: &'a mut Option<u32>,
+
+    #[redact(redactable)]
+    card: &'a mut RedactableCard,
+}
+
+#[derive(Redactable)]
+struct RedactableCard(String);
+impl std::fmt::Display for RedactableCard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[derive(Redact)]
+enum RedactMutRefEnum<'a> {
+    Tuple(#[redact(partial)] &'a mut String),
+    #[redact(all)]
+    Struct {
+        name: &'a mut String,
+    },
+}
+
+#[test]
+fn test_redact_mut_ref() {
+    let mut name = "John Doe".to_string();
+    let mut email = "john.doe@prima.it".to_string();
+    let mut age = Some(42);
+    let mut card = RedactableCard("4242".to_string());
+    assert_eq!(
+        format!(
+            "{:?}",
+            RedactMutRef {
+                name: &mut name,
+                email: &mut email,
+                age: &mut age,
+                card: &mut card,
+            }
+        ),
+        "RedactMutRef { name: \"**** ***\", email: joh*.***@****a.it, age: Some(**), card: **** }"
+    );
+
+    assert_eq!(
+        format!("{:?}", RedactMutRefEnum::Tuple(&mut name)),
+        "Tuple(\"Jo** *oe\")"
+    );
+    assert_eq!(
+        format!("{:?}", RedactMutRefEnum::Struct { name: &mut name }),
+        "Struct { name: \"**** ***\" }"
+    );
+}
+
+#[derive(Redact)]
+#[redact(deref_transparent, partial)]
+struct RedactDerefNewtype(Vec<String>);
+impl std::ops::Deref for RedactDerefNewtype {
+    type Target = Vec<String>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[derive(Redact)]
+#[redact(deref_transparent, display)]
+struct RedactDerefUnsized {
+    name: String,
+}
+impl std::ops::Deref for RedactDerefUnsized {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.name
+    }
+}
+
+#[test]
+fn test_redact_deref_transparent() {
+    let emails = RedactDerefNewtype(vec!["john.doe@prima.it".to_string(), "jane.doe@prima.it".to_string()]);
+    assert_eq!(format!("{emails:?}"), "[\"joh*.***@*****.**\", \"****.***@****a.it\"]");
+
+    let name = RedactDerefUnsized {
+        name: "John Doe".to_string(),
+    };
+    assert_eq!(format!("{name:?}"), "**** ***");
+}
+
+#[derive(Redact)]
+struct RedactCallbacks {
+    #[redact]
+    on_success: fn(u32) -> u32,
+
+    #[redact]
+    on_error: Box<dyn Fn(&str) + Send + Sync>,
+
+    #[redact(partial)]
+    on_retry: std::sync::Arc<dyn FnMut()>,
+
+    #[redact]
+    name: String,
+}
+
+#[derive(Redact)]
+enum RedactCallbackEnum {
+    #[redact(all)]
+    Callback(fn(), &'static dyn Fn()),
+}
+
+#[test]
+fn test_redact_callbacks() {
+    fn double(x: u32) -> u32 {
+        x * 2
+    }
+    fn noop() {}
+
+    let callbacks = RedactCallbacks {
+        on_success: double,
+        on_error: Box::new(|_| {}),
+        on_retry: std::sync::Arc::new(|| {}),
+        name: "John Doe".to_string(),
+    };
+    assert_eq!(
+        format!("{callbacks:?}"),
+        "RedactCallbacks { on_success: <fn>, on_error: <fn>, on_retry: <fn>, name: \"**** ***\" }"
+    );
+    assert_eq!(
+        format!("{:?}", RedactCallbackEnum::Callback(noop, &noop)),
+        "Callback(<fn>, <fn>)"
+    );
+}
+
+#[derive(Redactable)]
+#[redact(partial)]
+struct RedactableBounded<T: std::fmt::Display>(T);
+impl<T: std::fmt::Display> std::fmt::Display for RedactableBounded<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[derive(Redactable)]
+struct RedactableWhere<T>(T)
+where
+    T: std::fmt::Display;
+impl<T> std::fmt::Display for RedactableWhere<T>
+where
+    T: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Only `Display` for some `T`, so the derived impl needs its own bound.
+#[derive(Redactable)]
+struct RedactableUnbounded<'a, T: ?Sized>(&'a T);
+impl<T: std::fmt::Display + ?Sized> std::fmt::Display for RedactableUnbounded<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[derive(Redact)]
+struct RedactGenericRedactable<T: std::fmt::Display> {
+    #[redact(redactable)]
+    account: RedactableBounded<T>,
+}
+
+#[test]
+fn test_redactable_generics() {
+    assert_eq!(RedactableBounded(1234567890).redact(), "123****890");
+    assert_eq!(RedactableWhere("John Doe").redact(), "**** ***");
+    assert_eq!(RedactableUnbounded("John Doe").redact(), "**** ***");
+    assert_eq!(
+        format!(
+            "{:?}",
+            RedactGenericRedactable {
+                account: RedactableBounded("john.doe@prima.it")
+            }
+        ),
+        "RedactGenericRedactable { account: joh*.***@****a.it }"
+    );
+}
+
+/// A C-compatible customer record.
+#[repr(C)]
+#[derive(Redact, Clone, Copy)]
+struct ReprCCustomer {
+    /// The customer's ID.
+    #[redact(partial)]
+    id: u64,
+    #[redact]
+    pin: u16,
+}
+
+/// A packed customer record.
+#[repr(C, packed)]
+#[derive(Redact, Clone, Copy)]
+struct PackedCustomer {
+    #[redact(partial)]
+    id: u64,
+    #[redact]
+    pin: u16,
+    flags: u8,
+    #[redact]
+    referral: Option<u32>,
+}
+
+/// Packed, with the alignment given separately.
+#[repr(Rust, packed(2))]
+#[derive(Redact, Clone, Copy)]
+#[redact(all, partial)]
+struct PackedAll(u64, #[redact(skip)] u8, char);
+
+/// Packed, with fields that are only redacted under some configurations.
+#[repr(Rust, packed)]
+#[derive(Redact, Clone, Copy)]
+struct PackedCfg {
+    #[redact(cfg(not(feature = "prod-logging")))]
+    name: &'static str,
+    #[redact(partial, cfg(not(feature = "prod-logging")))]
+    id: u32,
+    #[redact(cfg(not(feature = "prod-logging")))]
+    balance: std::num::Wrapping<u64>,
+}
+
+#[test]
+fn test_reprs() {
+    let customer = ReprCCustomer {
+        id: 1234567890,
+        pin: 4321,
+    };
+    assert_eq!(format!("{customer:?}"), "ReprCCustomer { id: 123****890, pin: **** }");
+
+    let customer = PackedCustomer {
+        id: 1234567890,
+        pin: 4321,
+        flags: 7,
+        referral: Some(42),
+    };
+    assert_eq!(
+        format!("{customer:?}"),
+        "PackedCustomer { id: 123****890, pin: ****, flags: 7, referral: Some(**) }"
+    );
+    assert_eq!(
+        format!("{customer:#?}"),
+        "PackedCustomer {\n    id: 123****890,\n    pin: ****,\n    flags: 7,\n    referral: Some(\n        **,\n    ),\n}"
+    );
+
+    assert_eq!(
+        format!("{:?}", PackedAll(1234567890, 7, 'x')),
+        "PackedAll(123****890, 7, '*')"
+    );
+
+    let customer = PackedCfg {
+        name: "William",
+        id: 1234567890,
+        balance: std::num::Wrapping(1000),
+    };
+    #[cfg(not(feature = "prod-logging"))]
+    assert_eq!(
+        format!("{customer:?}"),
+        "PackedCfg { name: \"*******\", id: 123****890, balance: **** }"
+    );
+    #[cfg(feature = "prod-logging")]
+    assert_eq!(
+        format!("{customer:?}"),
+        "PackedCfg { name: \"William\", id: 1234567890, balance: 1000 }"
+    );
+}
+
+#[derive(Redact)]
+#[redact(all, partial)]
+struct TrustedVehicle {
+    license_plate: String,
+    make: String,
+}
+
+#[derive(Debug)]
+struct TrustedPolicy {
+    id: u32,
+    name: String,
+}
+
+/// The `InsuranceStatus` example from the docs, trusting the fields that redact themselves instead of skipping them.
+#[derive(Redact)]
+enum TrustedInsuranceStatus {
+    #[redact(all, partial, trust_inner(Box<CreditCard>, Vec<TrustedVehicle>))]
+    Insured {
+        #[redact(fixed = 12)]
+        policy: TrustedPolicy,
+
+        policy_started: String,
+        policy_expires: String,
+
+        payment_card: Box<CreditCard>,
+        vehicles: Vec<TrustedVehicle>,
+    },
+
+    Uninsured {
+        policies_available: Vec<TrustedPolicy>,
+    },
+}
+
+/// References to trusted types are trusted too.
+#[derive(Redact)]
+#[redact(all, trust_inner(TrustedVehicle))]
+struct TrustedCheckout<'a>(&'a TrustedVehicle, String);
+
+#[test]
+fn test_trust_inner() {
+    let vehicle = || TrustedVehicle {
+        license_plate: "AB123CD".to_string(),
+        make: "Fiat".to_string(),
+    };
+    let status = TrustedInsuranceStatus::Insured {
+        policy: TrustedPolicy {
+            id: 1,
+            name: "Premium".to_string(),
+        },
+        policy_started: "2024-01-01".to_string(),
+        policy_expires: "2025-01-01".to_string(),
+        payment_card: Box::new(CreditCard {
+            cvv: "098".to_string(),
+            number: "1234 5678 9012 3456".to_string(),
+            expiration: "12/34".to_string(),
+            name: "John Doe".to_string(),
+            billing_address: Address {
+                line1: "123 Fake Street".to_string(),
+                line2: "Apt. 1".to_string(),
+                house_or_flat_number: Some(64),
+                postcode: "12345".to_string(),
+                city: "London".to_string(),
+            },
+            issuer: CreditCardIssuer::Visa {
+                visa_data_1: "Hello".to_string(),
+                visa_data_2: "World".to_string(),
+            },
+            country: Country::UnitedKingdom,
+        }),
+        vehicles: vec![vehicle()],
+    };
+
+    let TrustedInsuranceStatus::Insured { payment_card, .. } = &status else {
+        unreachable!()
+    };
+    let debug = format!("{status:?}");
+    assert!(
+        debug.starts_with("Insured { policy: ************, policy_started: \"20**-**-01\""),
+        "{debug}"
+    );
+    assert!(debug.contains(&format!("payment_card: {payment_card:?}")), "{debug}");
+    assert!(debug.ends_with(&format!("vehicles: {:?} }}", [vehicle()])), "{debug}");
+    assert!(!debug.contains("AB123CD"), "{debug}");
+
+    assert_eq!(
+        format!("{:?}", TrustedCheckout(&vehicle(), "Gold".to_string())),
+        format!("TrustedCheckout({:?}, \"****\")", vehicle())
+    );
+}
+
+/// A type that can fail to format itself, e.g. because its data is encrypted at rest.
+struct EncryptedIban {
+    iban: &'static str,
+    key_available: bool,
+}
+impl EncryptedIban {
+    fn fmt_safe(&self) -> Result<String, std::fmt::Error> {
+        if self.key_available {
+            Ok(self.iban.to_string())
+        } else {
+            Err(std::fmt::Error)
+        }
+    }
+}
+
+#[derive(Redact)]
+struct BankAccount {
+    #[redact(partial, try_display = "self.fmt_safe")]
+    iban: EncryptedIban,
+    #[redact(fixed = 3, try_display = "self.fmt_safe")]
+    backup_iban: EncryptedIban,
+}
+
+#[test]
+fn test_try_display() {
+    let account = BankAccount {
+        iban: EncryptedIban {
+            iban: "IT60X0542811101000000123456",
+            key_available: true,
+        },
+        backup_iban: EncryptedIban {
+            iban: "IT60X0542811101000000123456",
+            key_available: false,
+        },
+    };
+    assert_eq!(
+        format!("{account:?}"),
+        "BankAccount { iban: IT6*********************456, backup_iban: *** }"
+    );
+
+    // A failing method doesn't fail the rest of the output, its placeholder is redacted in its place.
+    let account = BankAccount {
+        iban: EncryptedIban {
+            iban: "IT60X0542811101000000123456",
+            key_available: false,
+        },
+        ..account
+    };
+    assert_eq!(
+        format!("{account:?}"),
+        "BankAccount { iban: <unf*******ble>, backup_iban: *** }"
+    );
+}
+
+#[derive(Redact)]
+#[redact(max_depth = 2)]
+struct CategoryTree {
+    #[redact(partial)]
+    name: String,
+    children: Vec<CategoryTree>,
+}
+
+#[derive(Redact)]
+#[redact(max_depth = 3)]
+enum Expression {
+    Literal(#[redact] i64),
+    Negate(Box<Expression>),
+    Add(Box<Expression>, Box<Expression>),
+}
+
+#[test]
+fn test_max_depth() {
+    let leaf = |name: &str| CategoryTree {
+        name: name.to_string(),
+        children: vec![],
+    };
+    let tree = CategoryTree {
+        name: "Vehicles".to_string(),
+        children: vec![
+            CategoryTree {
+                name: "Motorbikes".to_string(),
+                children: vec![leaf("Scooters"), leaf("Mopeds")],
+            },
+            leaf("Cars"),
+        ],
+    };
+    assert_eq!(
+        format!("{tree:?}"),
+        "CategoryTree { name: \"Ve****es\", children: [CategoryTree { name: \"Mot****kes\", children: [..., ...] }, \
+         CategoryTree { name: \"****\", children: [] }] }"
+    );
+    // The depth is counted again from the start every time the value is formatted.
+    assert_eq!(format!("{tree:?}"), format!("{tree:?}"));
+
+    let expression = Expression::Add(
+        Box::new(Expression::Literal(1)),
+        Box::new(Expression::Negate(Box::new(Expression::Negate(Box::new(
+            Expression::Literal(2),
+        ))))),
+    );
+    assert_eq!(format!("{expression:?}"), "Add(Literal(*), Negate(Negate(...)))");
+    assert_eq!(
+        format!("{expression:#?}"),
+        "Add(\n    Literal(\n        *,\n    ),\n    Negate(\n        Negate(\n            ...,\n        ),\n    ),\n)"
+    );
+}
@@ -1,5 +1,11 @@
 mod code_coverage;
 mod compile_tests;
 mod redaction_tests;
+#[cfg(test)]
+mod redactor_tests;
+#[cfg(test)]
+mod stream_tests;
+#[cfg(test)]
+mod tracing_tests;
 
 pub use redaction_tests::{assert_has_sensitive_data, assert_no_sensitive_data, SENSITIVE_DATA};
@@ -0,0 +1,549 @@
+//! Tests for redacting data directly with a [`Redactor`], rather than through `#[derive(Redact)]`.
+
+use std::{borrow::Cow, collections::HashMap, num::NonZeroU8, str::FromStr};
+use veil::redactor::{RedactionLength, Redactor, RedactorBuilder};
+
+#[test]
+fn test_wrap_non_veil_nested_struct() {
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Address {
+        street: String,
+        zip: u32,
+    }
+
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct Customer {
+        name: String,
+        address: Address,
+        tags: Vec<&'static str>,
+    }
+
+    let customer = Customer {
+        name: "John Doe".to_string(),
+        address: Address {
+            street: "10 Downing Street".to_string(),
+            zip: 12345,
+        },
+        tags: vec!["vip"],
+    };
+
+    let redactor = Redactor::builder().build().unwrap();
+    assert_eq!(
+        format!("{:?}", redactor.wrap(&customer)),
+        "******** { ****: \"**** ***\", *******: ******* { ******: \"** ******* ******\", ***: ***** }, ****: [\"***\"] }"
+    );
+    assert_eq!(
+        format!("{:#?}", redactor.wrap(&customer)),
+        "******** {\n    ****: \"**** ***\",\n    *******: ******* {\n        ******: \"** ******* ******\",\n        ***: *****,\n    },\n    ****: [\n        \"***\",\n    ],\n}"
+    );
+
+    // Partial redaction applies to the output as a whole, so only its beginning and end are exposed.
+    let redactor = Redactor::builder().partial().build().unwrap();
+    assert_eq!(
+        format!("{:?}", redactor.wrap(&customer)),
+        "Cus***** { ****: \"**** ***\", *******: ******* { ******: \"** ******* ******\", ***: ***** }, ****: [\"vip\"] }"
+    );
+}
+
+#[test]
+fn test_debug_shows_config() {
+    assert_eq!(
+        format!("{:?}", Redactor::builder().build().unwrap()),
+        "Redactor { length: Full, char: '*', digits_only: false, cycle: None, cjk: false, preserve_class: None, pad: None, min_mask_len: None, min_partial_chars: None, expose_side: None, secret: false, hash: None, when: false }"
+    );
+    assert_eq!(
+        format!("{:?}", Redactor::builder().char('X').partial().build().unwrap()),
+        "Redactor { length: Partial, char: 'X', digits_only: false, cycle: None, cjk: false, preserve_class: None, pad: None, min_mask_len: None, min_partial_chars: None, expose_side: None, secret: false, hash: None, when: false }"
+    );
+    assert_eq!(
+        format!(
+            "{:?}",
+            Redactor::builder().min_mask_len(6).when(|data| data.contains('@')).build().unwrap()
+        ),
+        "Redactor { length: Full, char: '*', digits_only: false, cycle: None, cjk: false, preserve_class: None, pad: None, min_mask_len: Some(6), min_partial_chars: None, expose_side: None, secret: false, hash: None, when: true }"
+    );
+    assert_eq!(
+        format!(
+            "{:?}",
+            Redactor::from_str("fixed = 3, with = '#', digits_only").unwrap()
+        ),
+        "Redactor { length: Fixed(3), char: '#', digits_only: true, cycle: None, cjk: false, preserve_class: None, pad: None, min_mask_len: None, min_partial_chars: None, expose_side: None, secret: false, hash: None, when: false }"
+    );
+}
+
+#[test]
+fn test_accessors_reflect_config() {
+    let redactor = Redactor::builder().build().unwrap();
+    assert_eq!(redactor.mask_char(), '*');
+    assert_eq!(redactor.mask_mode(), RedactionLength::Full);
+    assert!(!redactor.digits_only());
+
+    let redactor = Redactor::builder().char('X').partial().build().unwrap();
+    assert_eq!(redactor.mask_char(), 'X');
+    assert_eq!(redactor.mask_mode(), RedactionLength::Partial);
+
+    let redactor = Redactor::builder().partial().expose_pct(25).build().unwrap();
+    assert_eq!(redactor.mask_mode(), RedactionLength::PartialPercent(25));
+
+    let redactor = Redactor::from_str("fixed = 3, digits_only").unwrap();
+    assert_eq!(redactor.mask_mode(), RedactionLength::Fixed(NonZeroU8::new(3).unwrap()));
+    assert!(redactor.digits_only());
+
+    let redactor = Redactor::from_str("mask_prefix = 2, mask_suffix = 1").unwrap();
+    assert_eq!(redactor.mask_mode(), RedactionLength::MaskEnds { prefix: 2, suffix: 1 });
+}
+
+#[test]
+fn test_from_env() {
+    // Tests run in parallel, so each case gets its own prefix.
+    let redactor = Redactor::from_env("VEIL_TEST_UNSET").unwrap();
+    assert_eq!(redactor.redact("John Doe".to_string()), "**** ***");
+
+    std::env::set_var("VEIL_TEST_PARTIAL_MODE", "partial");
+    std::env::set_var("VEIL_TEST_PARTIAL_CHAR", "#");
+    let redactor = Redactor::from_env("VEIL_TEST_PARTIAL").unwrap();
+    assert_eq!(redactor.redact("john.doe@prima.it".to_string()), "joh#.###@####a.it");
+
+    std::env::set_var("VEIL_TEST_FIXED_MODE", "fixed = 3");
+    std::env::set_var("VEIL_TEST_FIXED_CHAR", ",");
+    let redactor = Redactor::from_env("VEIL_TEST_FIXED").unwrap();
+    assert_eq!(redactor.redact("John Doe".to_string()), ",,,");
+
+    std::env::set_var("VEIL_TEST_FULL_MODE", " full ");
+    let redactor = Redactor::from_env("VEIL_TEST_FULL").unwrap();
+    assert_eq!(redactor.mask_mode(), RedactionLength::Full);
+
+    std::env::set_var("VEIL_TEST_BAD_MODE_MODE", "parital");
+    assert_eq!(
+        Redactor::from_env("VEIL_TEST_BAD_MODE").unwrap_err(),
+        "unknown or malformed redaction modifier"
+    );
+
+    std::env::set_var("VEIL_TEST_BAD_WITH_MODE", "partial, with = 'X'");
+    assert_eq!(
+        Redactor::from_env("VEIL_TEST_BAD_WITH").unwrap_err(),
+        "the `_MODE` environment variable can't set `with`, use the `_CHAR` variable instead"
+    );
+
+    // Only a `with` modifier is rejected, not every mode that happens to contain the word.
+    std::env::set_var("VEIL_TEST_WITHOUT_MODE", "partial, without");
+    assert_eq!(
+        Redactor::from_env("VEIL_TEST_WITHOUT").unwrap_err(),
+        "unknown or malformed redaction modifier"
+    );
+    std::env::set_var("VEIL_TEST_SPACED_WITH_MODE", "partial ,  with='X'");
+    assert_eq!(
+        Redactor::from_env("VEIL_TEST_SPACED_WITH").unwrap_err(),
+        "the `_MODE` environment variable can't set `with`, use the `_CHAR` variable instead"
+    );
+
+    std::env::set_var("VEIL_TEST_BAD_CHAR_CHAR", "XY");
+    assert_eq!(
+        Redactor::from_env("VEIL_TEST_BAD_CHAR").unwrap_err(),
+        "the `_CHAR` environment variable must be a single character"
+    );
+}
+
+#[test]
+fn test_expose_pct_requires_partial() {
+    assert_eq!(
+        Redactor::builder().expose_pct(10).build().unwrap_err(),
+        "`expose_pct` requires `partial`"
+    );
+    assert!(Redactor::builder().partial().expose_pct(50).build().is_err());
+    assert_eq!(
+        format!("{:?}", Redactor::builder().partial().expose_pct(49).build().unwrap()),
+        "Redactor { length: PartialPercent(49), char: '*', digits_only: false, cycle: None, cjk: false, preserve_class: None, pad: None, min_mask_len: None, min_partial_chars: None, expose_side: None, secret: false, hash: None, when: false }"
+    );
+}
+
+#[test]
+fn test_min_mask_len() {
+    let redactor = Redactor::builder().min_mask_len(6).build().unwrap();
+
+    // Below the floor, the mask is extended after the last redacted character.
+    assert_eq!(redactor.redact("ab".to_string()), "******");
+    assert_eq!(redactor.redact("a-b!".to_string()), "*-*****!");
+    assert_eq!(format!("{:?}", redactor.wrap(&"ab")), "\"******\"");
+
+    // At or above the floor, nothing changes.
+    assert_eq!(redactor.redact("abcdef".to_string()), "******");
+    assert_eq!(redactor.redact("John Doe".to_string()), "**** ***");
+
+    // There's nothing to mask, so nothing is added.
+    assert_eq!(redactor.redact(String::new()), "");
+    assert_eq!(redactor.redact("--".to_string()), "--");
+
+    assert_eq!(
+        Redactor::builder()
+            .char('X')
+            .min_mask_len(4)
+            .build()
+            .unwrap()
+            .redact("1".to_string()),
+        "XXXX"
+    );
+    assert_eq!(
+        Redactor::builder().min_mask_len(0).build().unwrap_err(),
+        "`min_mask_len` must be greater than zero"
+    );
+    assert_eq!(
+        Redactor::builder().partial().min_mask_len(8).build().unwrap_err(),
+        "`min_mask_len` can't be combined with `partial`"
+    );
+    assert_eq!(
+        Redactor::builder().fixed(3).min_mask_len(8).build().unwrap_err(),
+        "`min_mask_len` can't be combined with `fixed`"
+    );
+    assert_eq!(
+        Redactor::builder().mask_prefix(2).min_mask_len(8).build().unwrap_err(),
+        "`min_mask_len` can't be combined with `mask_prefix` or `mask_suffix`"
+    );
+}
+
+#[test]
+fn test_redact_display_matches_redact() {
+    let redactor = Redactor::builder().partial().build().unwrap();
+
+    let number: u64 = 1234567890;
+    assert_eq!(redactor.redact_display(&number), redactor.redact(number.to_string()));
+    assert_eq!(redactor.redact_display(&number), "123****890");
+
+    let ip = std::net::IpAddr::from([192, 168, 100, 254]);
+    assert_eq!(redactor.redact_display(&ip), redactor.redact(ip.to_string()));
+    assert_eq!(redactor.redact_display(&ip), "192.***.***.254");
+
+    let ip = std::net::IpAddr::from([0x2001, 0xdb8, 0, 0, 0, 0, 0, 1]);
+    assert_eq!(Redactor::builder().build().unwrap().redact_display(&ip), "****:***::*");
+}
+
+#[test]
+fn test_redact_when() {
+    let redactor = Redactor::builder()
+        .partial()
+        .when(|data| data.chars().all(|char| char.is_ascii_digit()))
+        .build()
+        .unwrap();
+
+    assert_eq!(redactor.redact("039845734895".to_string()), "039******895");
+    assert_eq!(redactor.redact("William".to_string()), "William");
+
+    // The predicate sees the whole string, not each word in it.
+    assert_eq!(redactor.redact("call 039845734895".to_string()), "call 039845734895");
+
+    assert_eq!(redactor.redact_display(&1234567890), "123****890");
+    assert_eq!(redactor.redact_display(&"10 Downing Street"), "10 Downing Street");
+
+    let map = HashMap::from([("name", "William".to_string()), ("phone", "039845734895".to_string())]);
+    assert_eq!(
+        redactor.redact_map(&map),
+        HashMap::from([("name", "William".to_string()), ("phone", "039******895".to_string())])
+    );
+
+    assert_eq!(format!("{}", redactor.wrap(&1234567890)), "123****890");
+    assert_eq!(format!("{}", redactor.wrap(&"William")), "William");
+
+    // `Debug` output of strings is quoted, so it never matches.
+    assert_eq!(format!("{:?}", redactor.wrap(&"039845734895")), "\"039845734895\"");
+    assert_eq!(format!("{:?}", redactor.wrap(&1234567890)), "123****890");
+}
+
+#[test]
+fn test_redact_when_whole_input() {
+    // The predicate is called with the whole input of these helpers, not with the parts they redact.
+    let redactor = Redactor::builder()
+        .when(|data| data.ends_with("@prima.it"))
+        .build()
+        .unwrap();
+    assert_eq!(redactor.redact_email("john.doe@prima.it"), "****.***@prima.it");
+    assert_eq!(redactor.redact_email("john.doe@example.com"), "john.doe@example.com");
+
+    let redactor = Redactor::builder()
+        .when(|data| data.starts_with("/home/"))
+        .build()
+        .unwrap();
+    assert_eq!(redactor.redact_path("/home/jdoe/notes.txt"), "/****/****/*****.txt");
+    assert_eq!(redactor.redact_path("/var/log/app.log"), "/var/log/app.log");
+
+    let redactor = Redactor::builder()
+        .when(|data| data.starts_with("https://prima.it/"))
+        .build()
+        .unwrap();
+    assert_eq!(
+        redactor.redact_query_string("https://prima.it/login?user=jdoe&lang=en", &["user"]),
+        "https://prima.it/login?user=****&lang=en"
+    );
+    assert_eq!(
+        redactor.redact_query_string("https://example.com/login?user=jdoe", &["user"]),
+        "https://example.com/login?user=jdoe"
+    );
+}
+
+#[test]
+fn test_redact_counting() {
+    let redactor = Redactor::builder().build().unwrap();
+    assert_eq!(redactor.redact_counting("John Doe"), ("**** ***".to_string(), 7));
+    assert_eq!(redactor.redact_counting("- -"), ("- -".to_string(), 0));
+
+    let redactor = Redactor::builder().partial().build().unwrap();
+    assert_eq!(
+        redactor.redact_counting("john.doe@prima.it"),
+        ("joh*.***@****a.it".to_string(), 8)
+    );
+
+    // Too short to expose anything.
+    assert_eq!(redactor.redact_counting("John"), ("****".to_string(), 4));
+
+    // The count is of the data's characters, not of the redaction characters written.
+    let redactor = Redactor::builder().min_mask_len(5).build().unwrap();
+    assert_eq!(redactor.redact_counting("ab"), ("*****".to_string(), 2));
+
+    let redactor = Redactor::from_str("fixed = 3").unwrap();
+    assert_eq!(redactor.redact_counting("John Doe"), ("***".to_string(), 7));
+
+    let redactor = Redactor::builder()
+        .when(|data| data.chars().all(|char| char.is_ascii_digit()))
+        .build()
+        .unwrap();
+    assert_eq!(redactor.redact_counting("1234"), ("****".to_string(), 4));
+    assert_eq!(redactor.redact_counting("William"), ("William".to_string(), 0));
+}
+
+#[test]
+fn test_redact_query_string() {
+    let redactor = Redactor::builder().partial().build().unwrap();
+
+    assert_eq!(
+        redactor.redact_query_string(
+            "https://prima.it/api/v1?token=sk_live_1234567890&user=john.doe%40prima.it&lang=en&token=abc#top",
+            &["token", "user"]
+        ),
+        "https://prima.it/api/v1?token=sk_l***_*******890&user=joh*.***%******a.it&lang=en&token=***#top"
+    );
+
+    let redactor = Redactor::builder().build().unwrap();
+    assert_eq!(
+        redactor.redact_query_string("token=abc&user=jdoe&flag&empty=", &["token", "user", "flag", "empty"]),
+        "token=***&user=****&flag&empty="
+    );
+    assert_eq!(redactor.redact_query_string("?user=jdoe", &["user"]), "?user=****");
+    assert_eq!(
+        redactor.redact_query_string("https://prima.it/login", &["user"]),
+        "https://prima.it/login"
+    );
+}
+
+#[test]
+fn test_redact_owned() {
+    let redactor = Redactor::builder().partial().build().unwrap();
+    let full = Redactor::builder().build().unwrap();
+    let blocks = Redactor::builder().char('█').build().unwrap();
+
+    let with_capacity = |data: &str, capacity: usize| {
+        let mut buffer = String::with_capacity(capacity);
+        buffer.push_str(data);
+        buffer
+    };
+
+    for (redactor, data) in [
+        // Shrinking, as each `*` takes up fewer bytes than the character it redacts
+        (&full, "Ünïcödé Wåñg"),
+        // Growing
+        (&blocks, "John Doe"),
+        (&redactor, "john.doe@prima.it"),
+        (&redactor, ""),
+    ] {
+        let expected = redactor.redact(data.to_string());
+
+        // Enough spare capacity for the redacted output, so the allocation is reused.
+        let buffer = with_capacity(data, data.len() + expected.len());
+        let ptr = buffer.as_ptr();
+        let redacted = redactor.redact_owned(buffer);
+        assert_eq!(redacted, expected);
+        if !data.is_empty() {
+            assert_eq!(redacted.as_ptr(), ptr);
+        }
+
+        // Not enough spare capacity, so a new string is allocated.
+        for capacity in [data.len(), data.len() + 1] {
+            assert_eq!(redactor.redact_owned(with_capacity(data, capacity)), expected);
+        }
+    }
+
+    assert_eq!(blocks.redact("John Doe".to_string()), "████ ███");
+    assert_eq!(full.redact("Ünïcödé Wåñg".to_string()), "******* ****");
+}
+
+#[test]
+fn test_redact_email() {
+    let redactor = Redactor::builder().build().unwrap();
+
+    // Typical addresses, including subaddresses and dots in either part.
+    assert_eq!(redactor.redact_email("jdoe@prima.it"), "****@prima.it");
+    assert_eq!(
+        redactor.redact_email("john.doe@mail.prima.co.uk"),
+        "****.***@mail.prima.co.uk"
+    );
+    assert_eq!(redactor.redact_email("john.doe+tag@prima.it"), "****.***+***@prima.it");
+
+    // Malformed addresses.
+    assert_eq!(redactor.redact_email("john.doe"), "****.***");
+    assert_eq!(redactor.redact_email("@prima.it"), "@prima.it");
+    assert_eq!(redactor.redact_email("john.doe@"), "****.***@");
+    assert_eq!(redactor.redact_email("\"john@doe\"@prima.it"), "\"****@***\"@prima.it");
+    assert_eq!(redactor.redact_email(""), "");
+
+    // The local part is redacted with the configured flags.
+    let partial = Redactor::builder().partial().char('X').build().unwrap();
+    assert_eq!(partial.redact_email("william.smith@prima.it"), "wilXXXX.XXith@prima.it");
+}
+
+#[test]
+fn test_redact_path() {
+    let redactor = Redactor::builder().build().unwrap();
+
+    // Nested paths, with and without an extension.
+    assert_eq!(
+        redactor.redact_path("/var/log/app/server.log"),
+        "/***/***/***/******.log"
+    );
+    assert_eq!(redactor.redact_path("data/export.tar.gz"), "****/******.***.gz");
+    assert_eq!(redactor.redact_path("/etc/hostname"), "/***/********");
+    assert_eq!(redactor.redact_path("logs/2024/"), "****/****/");
+
+    // Windows and mixed separators.
+    assert_eq!(
+        redactor.redact_path(r"C:\Users\jdoe\report.json"),
+        r"*:\*****\****\******.json"
+    );
+    assert_eq!(redactor.redact_path(r"home/jdoe\id.txt"), r"****/****\**.txt");
+
+    // Relative components, repeated separators and dotfiles.
+    assert_eq!(redactor.redact_path("../jdoe/./notes.md"), "../****/./*****.md");
+    assert_eq!(redactor.redact_path("//srv//share"), "//***//*****");
+    assert_eq!(redactor.redact_path("/home/jdoe/.bashrc"), "/****/****/.******");
+    assert_eq!(redactor.redact_path("invoice."), "*******.");
+    assert_eq!(redactor.redact_path(""), "");
+
+    // Names are redacted with the configured flags.
+    let partial = Redactor::builder().partial().char('X').build().unwrap();
+    assert_eq!(
+        partial.redact_path("/home/william/passport.pdf"),
+        "/XXXX/wiXXXam/paXXXXrt.pdf"
+    );
+}
+
+#[test]
+fn test_redact_borrowed() {
+    let redactor = Redactor::builder().build().unwrap();
+
+    // Borrowed and unchanged.
+    assert!(matches!(redactor.redact_borrowed("- -"), Cow::Borrowed("- -")));
+    assert!(matches!(redactor.redact_borrowed(""), Cow::Borrowed("")));
+
+    // Borrowed and changed, including data that only differs after the first character.
+    assert!(matches!(redactor.redact_borrowed("John Doe"), Cow::Owned(redacted) if redacted == "**** ***"));
+    assert!(matches!(redactor.redact_borrowed("-- a"), Cow::Owned(redacted) if redacted == "-- *"));
+
+    // Output that only adds to the data, such as padding, isn't unchanged either.
+    let padded = Redactor::from_str("pad = 5").unwrap();
+    assert!(matches!(padded.redact_borrowed("--"), Cow::Owned(redacted) if redacted == "--   "));
+
+    // Owned data reuses its allocation, whether or not it's changed.
+    let mut data = String::with_capacity(64);
+    data.push_str("John Doe");
+    let buffer = data.as_ptr();
+    let redacted = redactor.redact_borrowed(data);
+    assert_eq!(redacted, "**** ***");
+    assert_eq!(redacted.as_ptr(), buffer);
+
+    let data = "- -".to_string();
+    let buffer = data.as_ptr();
+    assert_eq!(redactor.redact_borrowed(data).as_ptr(), buffer);
+
+    let redactor = Redactor::builder()
+        .when(|data| data.chars().all(|char| char.is_ascii_digit()))
+        .build()
+        .unwrap();
+    assert!(matches!(redactor.redact_borrowed("William"), Cow::Borrowed("William")));
+    assert_eq!(redactor.redact_borrowed("1234"), "****");
+}
+
+#[test]
+fn test_build_static() {
+    let redactor = RedactorBuilder::new().char('X').partial().build_static().unwrap();
+    let threads: Vec<_> = ["John Doe", "john.doe@prima.it"]
+        .into_iter()
+        .map(|data| std::thread::spawn(move || redactor.redact(data.to_string())))
+        .collect();
+    let redacted: Vec<_> = threads.into_iter().map(|thread| thread.join().unwrap()).collect();
+    assert_eq!(redacted, ["JoXX Xoe", "johX.XXX@XXXXa.it"]);
+
+    assert_eq!(
+        RedactorBuilder::new().expose_pct(10).build_static().unwrap_err(),
+        "`expose_pct` requires `partial`"
+    );
+}
+
+#[test]
+fn test_redact_map() {
+    let redactor = Redactor::builder().char('X').build().unwrap();
+
+    let mut map = HashMap::from([
+        ("name".to_string(), "William".to_string()),
+        ("phone".to_string(), "039845734895".to_string()),
+        ("empty".to_string(), String::new()),
+    ]);
+
+    let redacted = redactor.redact_map(&map);
+    assert_eq!(
+        redacted,
+        HashMap::from([
+            ("name".to_string(), "XXXXXXX".to_string()),
+            ("phone".to_string(), "XXXXXXXXXXXX".to_string()),
+            ("empty".to_string(), String::new()),
+        ])
+    );
+
+    redactor.redact_map_in_place(&mut map);
+    assert_eq!(map, redacted);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_redact_json_str() {
+    let redactor = Redactor::builder().partial().build().unwrap();
+
+    let json = r#"{
+        "name": "William",
+        "age": 42,
+        "verified": true,
+        "deleted_at": null,
+        "address": {
+            "line1": "10 Downing Street",
+            "coordinates": [51.5034, -0.1276]
+        },
+        "phones": ["039845734895", { "label": "work", "number": "0123456789" }]
+    }"#;
+
+    let redacted: serde_json::Value = serde_json::from_str(&redactor.redact_json_str(json).unwrap()).unwrap();
+    assert_eq!(
+        redacted,
+        serde_json::json!({
+            "name": "Wi***am",
+            "age": 42,
+            "verified": true,
+            "deleted_at": null,
+            "address": {
+                "line1": "10 D****** ***eet",
+                "coordinates": [51.5034, -0.1276]
+            },
+            "phones": ["039******895", { "label": "****", "number": "012****789" }]
+        })
+    );
+
+    assert_eq!(redactor.redact_json_str(r#""William""#).unwrap(), r#""Wi***am""#);
+    assert!(redactor.redact_json_str("{ not json").is_err());
+}
@@ -0,0 +1,157 @@
+//! Tests that redacting a stream with [`RedactorWriter`] matches redacting all of it at once.
+
+use std::io::{self, Write};
+use veil::{redactor::Redactor, stream::RedactorWriter};
+
+const INPUT: &str =
+    "William Assicurazioni\n039845734895 — 10 Downing Street\r\nÜnïcödé 山田太郎\n\nno trailing newline";
+
+fn redact_in_chunks(redactor: Redactor, chunk_size: usize) -> String {
+    let mut writer = RedactorWriter::new(redactor, Vec::new());
+    for chunk in INPUT.as_bytes().chunks(chunk_size) {
+        writer.write_all(chunk).unwrap();
+    }
+    writer.finish().unwrap();
+    String::from_utf8(writer.get_ref().clone()).unwrap()
+}
+
+#[test]
+fn test_stream_chunked_writes_match_single_write() {
+    let redactors: [fn() -> Redactor; 4] = [
+        || Redactor::builder().build().unwrap(),
+        || Redactor::builder().partial().build().unwrap(),
+        || Redactor::builder().char('█').build().unwrap(),
+        || Redactor::builder().min_mask_len(8).build().unwrap(),
+    ];
+    for redactor in redactors {
+        let expected = redact_in_chunks(redactor(), INPUT.len());
+        for chunk_size in 1..=INPUT.len() {
+            assert_eq!(
+                redact_in_chunks(redactor(), chunk_size),
+                expected,
+                "chunk size {chunk_size}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_stream_matches_redactor() {
+    let redactor = Redactor::builder().partial().build().unwrap();
+    let expected = INPUT
+        .split_inclusive('\n')
+        .map(|line| redactor.redact(line.to_string()))
+        .collect::<String>();
+    assert_eq!(
+        redact_in_chunks(Redactor::builder().partial().build().unwrap(), 7),
+        expected
+    );
+
+    // Full redaction doesn't depend on the rest of the line, so it's the same as redacting everything at once.
+    let redactor = Redactor::builder().build().unwrap();
+    assert_eq!(
+        redact_in_chunks(Redactor::builder().build().unwrap(), 3),
+        redactor.redact(INPUT.to_string())
+    );
+}
+
+#[test]
+fn test_stream_full_redaction_without_newlines() {
+    let mut writer = RedactorWriter::new(Redactor::builder().build().unwrap(), Vec::new());
+
+    // Only the run of redacted characters at the end is held back, not the whole line.
+    writer.write_all("John Doe, Ü".as_bytes()).unwrap();
+    assert_eq!(writer.get_ref(), b"**** ***, ");
+    writer.write_all(&"ï ".as_bytes()[..1]).unwrap();
+    assert_eq!(writer.get_ref(), b"**** ***, ");
+    writer.write_all(&"ï ".as_bytes()[1..]).unwrap();
+    assert_eq!(writer.get_ref(), b"**** ***, ** ");
+
+    // Partial redaction still needs the whole line.
+    let mut writer = RedactorWriter::new(Redactor::builder().partial().build().unwrap(), Vec::new());
+    writer.write_all(b"John Doe, ").unwrap();
+    assert!(writer.get_ref().is_empty());
+}
+
+#[test]
+fn test_stream_finishes_on_drop() {
+    let mut output = Vec::new();
+    {
+        let mut writer = RedactorWriter::new(Redactor::builder().build().unwrap(), &mut output);
+        writer.write_all(b"John").unwrap();
+        assert_eq!(writer.get_ref().len(), 0);
+    }
+    assert_eq!(output, b"****");
+}
+
+#[test]
+fn test_stream_invalid_utf8() {
+    let mut writer = RedactorWriter::new(Redactor::builder().build().unwrap(), Vec::new());
+    writer.write_all(b"Jo\xffhn\n").unwrap();
+    assert_eq!(String::from_utf8(writer.get_ref().clone()).unwrap(), "**\u{fffd}**\n");
+}
+
+#[test]
+fn test_stream_write_error_is_not_repeated() {
+    /// Fails every write while `fail` is set.
+    #[derive(Default)]
+    struct Failing {
+        output: Vec<u8>,
+        fail: bool,
+    }
+    impl Write for Failing {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.fail {
+                return Err(io::ErrorKind::Other.into());
+            }
+            self.output.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut writer = RedactorWriter::new(Redactor::builder().build().unwrap(), Failing::default());
+    writer.get_mut().fail = true;
+
+    // The data is consumed even though writing it fails, so retrying can't redact it twice.
+    assert_eq!(writer.write(b"John ").unwrap(), 5);
+    assert!(writer.get_ref().output.is_empty());
+
+    // The error is returned by the next write instead, which doesn't consume its data.
+    assert!(writer.write(b"Doe\n").is_err());
+
+    writer.get_mut().fail = false;
+    assert_eq!(writer.write(b"Doe\n").unwrap(), 4);
+    writer.finish().unwrap();
+    assert_eq!(writer.get_ref().output, b"**** ***\n");
+}
+
+#[test]
+fn test_stream_long_line_in_small_chunks() {
+    // A single 2 MiB line with no point to split it at before its end, written 64 bytes at a time. Each byte is only
+    // scanned once, so this takes as long as writing it at once.
+    let line = "Assicurazioni".repeat(2 * 1024 * 1024 / 13);
+    let data = format!("{line}\n{line}");
+
+    let redactors: [fn() -> Redactor; 2] = [
+        || Redactor::builder().partial().build().unwrap(),
+        || Redactor::builder().build().unwrap(),
+    ];
+    for redactor in redactors {
+        let mut writer = RedactorWriter::new(redactor(), Vec::new());
+        for chunk in data.as_bytes().chunks(64) {
+            writer.write_all(chunk).unwrap();
+        }
+        writer.finish().unwrap();
+
+        let expected = format!(
+            "{}\n{}",
+            redactor().redact(line.clone()),
+            redactor().redact(line.clone())
+        );
+        assert!(writer.get_ref() == expected.as_bytes());
+    }
+}
@@ -0,0 +1,138 @@
+//! Tests that redaction survives `tracing`'s field capture, which is how most users actually log redacted values.
+
+use crate::SENSITIVE_DATA;
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+use veil::{Redact, RedactExt, Redactable};
+
+#[derive(Clone, Default)]
+struct CapturedOutput(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for CapturedOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Runs `f` with a `tracing` subscriber installed for the current thread, returning everything it logged.
+fn capture(f: impl FnOnce()) -> String {
+    let output = CapturedOutput::default();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer({
+            let output = output.clone();
+            move || output.clone()
+        })
+        .with_ansi(false)
+        .with_max_level(tracing::Level::TRACE)
+        .finish();
+
+    tracing::subscriber::with_default(subscriber, f);
+
+    let captured = output.0.lock().unwrap();
+    String::from_utf8(captured.clone()).unwrap()
+}
+
+fn assert_no_sensitive_logs(f: impl FnOnce()) {
+    let logs = capture(f);
+    assert!(!logs.is_empty(), "nothing was logged");
+    for sensitive in SENSITIVE_DATA {
+        assert!(
+            !logs.contains(sensitive),
+            "{logs:?} contains sensitive data: {sensitive:?}"
+        );
+    }
+}
+
+#[derive(Redactable)]
+struct Address(&'static str);
+impl std::fmt::Display for Address {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(fmt)
+    }
+}
+
+#[derive(Redact)]
+struct Customer {
+    #[redact(partial)]
+    name: &'static str,
+    #[redact]
+    company: Option<&'static str>,
+    #[redact(redactable)]
+    address: Address,
+}
+
+#[derive(Redact)]
+enum Payment {
+    #[redact(variant)]
+    SensitiveVariant {
+        #[redact]
+        iban: String,
+    },
+}
+
+fn customer() -> Customer {
+    Customer {
+        name: SENSITIVE_DATA[0],
+        company: Some(SENSITIVE_DATA[1]),
+        address: Address(SENSITIVE_DATA[3]),
+    }
+}
+
+#[test]
+fn test_tracing_capture_sees_plaintext() {
+    // Makes sure the other tests can't pass just because nothing reaches the subscriber.
+    let logs = capture(|| tracing::info!(name = SENSITIVE_DATA[0], "plaintext"));
+    assert!(logs.contains(SENSITIVE_DATA[0]), "{logs:?}");
+}
+
+#[test]
+fn test_tracing_debug_fields() {
+    assert_no_sensitive_logs(|| {
+        let customer = customer();
+        let payment = Payment::SensitiveVariant {
+            iban: SENSITIVE_DATA[2].to_string(),
+        };
+        tracing::info!(?customer, ?payment, "debug fields");
+        tracing::warn!(customer = ?customer, "named debug field");
+        tracing::error!(customer.name = ?customer, "dotted debug field");
+    });
+}
+
+#[test]
+fn test_tracing_display_fields() {
+    assert_no_sensitive_logs(|| {
+        let customer = customer();
+        tracing::info!(address = %customer.address.redact(), "redactable");
+        tracing::info!(name = %customer.name.redacted_partial(), "inline redaction");
+        tracing::info!(company = %SENSITIVE_DATA[1].redacted_with('X'), "inline redaction with");
+    });
+}
+
+#[test]
+fn test_tracing_message_arguments() {
+    assert_no_sensitive_logs(|| {
+        let customer = customer();
+        tracing::info!("customer {:?} at {}", customer, customer.address.redact());
+        tracing::debug!("named {} in message", SENSITIVE_DATA[0].redacted());
+    });
+}
+
+#[test]
+fn test_tracing_span_fields() {
+    assert_no_sensitive_logs(|| {
+        let customer = customer();
+        let span = tracing::info_span!("checkout", ?customer);
+        let _guard = span.enter();
+        tracing::info!("inside span");
+
+        span.record("customer", tracing::field::debug(&customer));
+        tracing::info!("after record");
+    });
+}
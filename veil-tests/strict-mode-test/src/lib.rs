@@ -0,0 +1,42 @@
+#![cfg_attr(not(test), allow(unused))]
+//! Simple test that ensures veil's strict mode catches redaction that produces the original value
+
+use veil::Redact;
+
+#[derive(Redact)]
+struct Customer {
+    #[redact(partial)]
+    name: &'static str,
+
+    #[redact]
+    nickname: Option<&'static str>,
+
+    #[redact(digits_only)]
+    currency: &'static str,
+
+    #[redact(mask_prefix = 0)]
+    misconfigured: &'static str,
+}
+
+#[test]
+fn test_strict_mode() {
+    veil::strict_mode();
+
+    let customer = |misconfigured| Customer {
+        name: "John Doe",
+        nickname: None,
+        currency: "EUR",
+        misconfigured,
+    };
+
+    // Data without any redactable characters can't be redacted, so it's fine for it to be shown as-is.
+    assert_eq!(
+        format!("{:?}", customer("")),
+        "Customer { name: \"Jo** *oe\", nickname: None, currency: \"EUR\", misconfigured: \"\" }"
+    );
+
+    let panic = std::panic::catch_unwind(|| format!("{:?}", customer("secret"))).unwrap_err();
+    let message = panic.downcast_ref::<&str>().unwrap();
+    assert!(message.starts_with("veil strict mode"), "{message}");
+    assert!(!message.contains("secret"), "{message}");
+}